@@ -0,0 +1,49 @@
+// Copyright 2019 Mitchell Kember. Subject to the MIT License.
+
+//! Benchmarks `Table::find_all` and `Table::find_by_headers` on pages with
+//! many small tables, where the cost of setting up each table dominates.
+//!
+//! Run with `cargo bench --bench many_tables`.
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use table_extract::Table;
+
+/// Generates a page with `tables` tables of `rows` rows each.
+fn page(tables: usize, rows: usize) -> String {
+    let mut html = String::from("<html><body>");
+    for t in 0..tables {
+        html.push_str("<table><tr><th>Id</th><th>Name</th></tr>");
+        for r in 0..rows {
+            html.push_str(&format!(
+                "<tr><td>{}</td><td><a href=\"/{}\">{}</a></td></tr>",
+                r, r, t
+            ));
+        }
+        html.push_str("</table>");
+    }
+    html.push_str("<table><tr><th>Last</th></tr></table></body></html>");
+    html
+}
+
+fn many_tables(c: &mut Criterion) {
+    let mut group = c.benchmark_group("many_tables");
+    group.sample_size(10);
+    for &(tables, rows) in &[(1000, 1), (1000, 5), (5000, 2)] {
+        let html = page(tables, rows);
+        let id = format!("{}x{}", tables, rows);
+        group.bench_with_input(
+            BenchmarkId::new("find_all", &id),
+            &html,
+            |b, html| b.iter(|| Table::find_all(html)),
+        );
+        group.bench_with_input(
+            BenchmarkId::new("find_by_headers", &id),
+            &html,
+            |b, html| b.iter(|| Table::find_by_headers(html, &["Last"])),
+        );
+    }
+    group.finish();
+}
+
+criterion_group!(benches, many_tables);
+criterion_main!(benches);
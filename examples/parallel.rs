@@ -0,0 +1,55 @@
+// Copyright 2019 Mitchell Kember. Subject to the MIT License.
+
+//! Compares `Table::find_all` with `Table::find_all_parallel`.
+//!
+//! Run with `cargo run --release --features rayon --example parallel`.
+
+use std::time::{Duration, Instant};
+use table_extract::Table;
+
+/// Generates a page with `tables` tables of `rows` rows each.
+fn page(tables: usize, rows: usize) -> String {
+    let mut html = String::from("<html><body>");
+    for t in 0..tables {
+        html.push_str("<table><tr><th>Id</th><th>Name</th><th>Notes</th></tr>");
+        for r in 0..rows {
+            html.push_str(&format!(
+                "<tr><td>{}</td><td>Item <b>{}</b></td>\
+                 <td><a href=\"/{}\">link</a> and text</td></tr>",
+                r, t, r
+            ));
+        }
+        html.push_str("</table>");
+    }
+    html.push_str("</body></html>");
+    html
+}
+
+/// Returns the fastest of several runs of `f`.
+fn time<F: Fn() -> Vec<Table>>(f: F) -> Duration {
+    (0..5)
+        .map(|_| {
+            let start = Instant::now();
+            assert!(!f().is_empty());
+            start.elapsed()
+        })
+        .min()
+        .unwrap()
+}
+
+fn main() {
+    println!("threads: {}", rayon::current_num_threads());
+    println!(
+        "{:>7} {:>7} {:>12} {:>12}",
+        "tables", "rows", "serial", "parallel"
+    );
+    for &(tables, rows) in &[(100, 10), (10, 100), (10, 1000), (50, 1000)] {
+        let html = page(tables, rows);
+        let serial = time(|| Table::find_all(&html));
+        let parallel = time(|| Table::find_all_parallel(&html));
+        println!(
+            "{:>7} {:>7} {:>12?} {:>12?}",
+            tables, rows, serial, parallel
+        );
+    }
+}
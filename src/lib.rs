@@ -3,15 +3,27 @@
 //! Utility for extracting data from HTML tables.
 //!
 //! This library allows you to parse tables from HTML documents and iterate over
-//! their rows. There are three entry points:
+//! their rows. There are four entry points:
 //!
 //! - [`Table::find_first`] finds the first table.
+//! - [`Table::find_all`] finds all the tables.
 //! - [`Table::find_by_id`] finds a table by its HTML id.
 //! - [`Table::find_by_headers`] finds a table that has certain headers.
 //!
-//! Each of these returns an `Option<`[`Table`]`>`, since there might not be any
-//! matching table in the HTML. Once you have a table, you can iterate over it
-//! and access the contents of each [`Row`].
+//! Apart from `find_all`, each of these returns an `Option<`[`Table`]`>`, since
+//! there might not be any matching table in the HTML. Once you have a table,
+//! you can iterate over it and access the contents of each [`Row`].
+//!
+//! Each entry point also has `_from_html` and `_from_elem` variants, which
+//! search an already parsed [`Html`] document or a particular [`ElementRef`]
+//! within one.
+//!
+//! By default, each cell's inner HTML is used as its content. To extract plain
+//! text, collapse whitespace, or otherwise change how tables are parsed, use a
+//! [`TableBuilder`].
+//!
+//! For large tables that are only read, [`BorrowedTable`] borrows the text of
+//! the cells from a parsed [`Html`] document rather than copying it.
 //!
 //! # Examples
 //!
@@ -56,13 +68,34 @@
 //!
 //! [`Table`]: struct.Table.html
 //! [`Row`]: struct.Row.html
+//! [`TableBuilder`]: struct.TableBuilder.html
+//! [`BorrowedTable`]: struct.BorrowedTable.html
 //! [`Table::find_first`]: struct.Table.html#method.find_first
+//! [`Table::find_all`]: struct.Table.html#method.find_all
 //! [`Table::find_by_id`]: struct.Table.html#method.find_by_id
 //! [`Table::find_by_headers`]: struct.Table.html#method.find_by_headers
+//! [`Html`]: https://docs.rs/scraper/*/scraper/html/struct.Html.html
+//! [`ElementRef`]: https://docs.rs/scraper/*/scraper/element_ref/struct.ElementRef.html
 
+#[cfg(feature = "ndarray")]
+use ndarray::Array2;
+use once_cell::sync::Lazy;
+#[cfg(feature = "polars")]
+use polars::prelude::{DataFrame, PolarsResult};
 use scraper::element_ref::ElementRef;
 use scraper::{Html, Selector};
+use std::borrow::Cow;
+use std::cmp::Ordering;
 use std::collections::HashMap;
+use std::convert::TryFrom;
+use std::error::Error;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::io::{self, Read};
+#[cfg(feature = "ndarray")]
+use std::num::ParseFloatError;
+use std::str::FromStr;
+use std::sync::Arc;
 
 /// A map from `<th>` table headers to their zero-based positions.
 ///
@@ -76,571 +109,8388 @@ use std::collections::HashMap;
 /// ```
 ///
 /// The `Headers` for this table would map "Name" to 0 and "Age" to 1.
+///
+/// If a header name appears more than once, it maps to the position of the
+/// first occurrence.
 pub type Headers = HashMap<String, usize>;
 
-/// A parsed HTML table.
-///
-/// See [the module level documentation](index.html) for more.
+/// How the contents of each table cell are extracted.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Hash)]
+pub enum TextMode {
+    /// Use the inner HTML of the cell, including any tags. Character entities
+    /// such as `&amp;` are left as is. This is the default.
+    #[default]
+    Html,
+    /// Use the text of the cell, with tags removed and character entities
+    /// decoded. Text nodes are concatenated exactly as they appear in the
+    /// source, so whitespace between inline elements is kept (`a <b>b</b>`
+    /// becomes `a b`), but none is added (`<b>a</b><b>b</b>` becomes `ab`).
+    Text,
+}
+
+/// An error explaining why a table could not be found or combined with
+/// others.
 #[derive(Clone, Debug, Eq, PartialEq)]
-pub struct Table {
-    headers: Headers,
-    data: Vec<Vec<String>>,
+pub enum TableError {
+    /// The HTML does not contain a matching table.
+    NoTableFound,
+    /// A CSS selector could not be parsed. This happens when passing an
+    /// invalid selector to
+    /// [`Table::try_find_by_selector`](struct.Table.html#method.try_find_by_selector)
+    /// or
+    /// [`TableBuilder::cell_selector`](struct.TableBuilder.html#method.cell_selector),
+    /// or when searching for an empty id.
+    InvalidSelector(String),
+    /// The headers of a table passed to
+    /// [`Table::concat`](struct.Table.html#method.concat) differ from those of
+    /// the first table.
+    HeaderMismatch {
+        /// The position of the table in the slice passed to `concat`.
+        index: usize,
+        /// The header names of the first table, in column order.
+        expected: Vec<String>,
+        /// The header names of the mismatched table, in column order.
+        found: Vec<String>,
+    },
 }
 
-impl Table {
-    /// Finds the first table in `html`.
-    pub fn find_first(html: &str) -> Option<Table> {
-        let html = Html::parse_fragment(html);
-        html.select(&css("table")).next().map(Table::new)
+impl fmt::Display for TableError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            TableError::NoTableFound => write!(f, "no matching table found"),
+            TableError::InvalidSelector(selector) => {
+                write!(f, "invalid CSS selector: {}", selector)
+            }
+            TableError::HeaderMismatch {
+                index,
+                expected,
+                found,
+            } => write!(
+                f,
+                "headers of table {} do not match: expected {:?}, found {:?}",
+                index, expected, found
+            ),
+        }
     }
+}
 
-    /// Finds the table in `html` with an id of `id`.
-    pub fn find_by_id(html: &str, id: &str) -> Option<Table> {
-        let html = Html::parse_fragment(html);
-        let selector = format!("table#{}", id);
-        Selector::parse(&selector)
-            .ok()
-            .as_ref()
-            .map(|s| html.select(s))
-            .and_then(|mut s| s.next())
-            .map(Table::new)
-    }
+impl Error for TableError {}
 
-    /// Finds the table in `html` whose first row contains all of the headers
-    /// specified in `headers`. The order does not matter.
-    ///
-    /// If `headers` is empty, this is the same as
-    /// [`find_first`](#method.find_first).
-    pub fn find_by_headers<T>(html: &str, headers: &[T]) -> Option<Table>
-    where
-        T: AsRef<str>,
-    {
-        if headers.is_empty() {
-            return Table::find_first(html);
+/// An error explaining why a row could not be converted with
+/// [`FromRow`](trait.FromRow.html).
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum RowError {
+    /// The row has no cell underneath the header, either because the table
+    /// has no such header or because the row is too short.
+    MissingColumn(String),
+    /// The cell underneath the header could not be parsed.
+    InvalidValue {
+        /// The header of the cell.
+        header: String,
+        /// The contents of the cell.
+        value: String,
+        /// The message of the error returned when parsing the cell.
+        message: String,
+    },
+}
+
+impl fmt::Display for RowError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            RowError::MissingColumn(header) => {
+                write!(f, "missing column: {}", header)
+            }
+            RowError::InvalidValue {
+                header,
+                value,
+                message,
+            } => write!(
+                f,
+                "invalid value {:?} in column {}: {}",
+                value, header, message
+            ),
         }
+    }
+}
 
-        let sel_table = css("table");
-        let sel_tr = css("tr");
-        let sel_th = css("th");
+impl Error for RowError {}
 
-        let html = Html::parse_fragment(html);
-        html.select(&sel_table)
-            .find(|table| {
-                table.select(&sel_tr).next().map_or(false, |tr| {
-                    let cells = select_cells(tr, &sel_th);
-                    headers.iter().all(|h| contains_str(&cells, h.as_ref()))
-                })
-            })
-            .map(Table::new)
-    }
+/// A type that can be created from a [`Row`](struct.Row.html) of a table.
+///
+/// Implement this to convert the rows of a table into your own type with
+/// [`Table::deserialize`](struct.Table.html#method.deserialize). The
+/// [`Row::try_get`](struct.Row.html#method.try_get) and
+/// [`Row::try_parse`](struct.Row.html#method.try_parse) methods return
+/// `RowError`s, so they work well with the `?` operator:
+///
+/// ```
+/// use table_extract::{FromRow, Row, RowError, Table};
+///
+/// struct Person {
+///     name: String,
+///     age: u32,
+/// }
+///
+/// impl FromRow for Person {
+///     fn from_row(row: &Row) -> Result<Person, RowError> {
+///         Ok(Person {
+///             name: row.try_get("Name")?.to_string(),
+///             age: row.try_parse("Age")?,
+///         })
+///     }
+/// }
+///
+/// let html = r#"
+///     <table>
+///         <tr><th>Name</th><th>Age</th></tr>
+///         <tr><td>John</td><td>20</td></tr>
+///         <tr><td>May</td><td>unknown</td></tr>
+///     </table>
+/// "#;
+/// let table = Table::find_first(html).unwrap();
+/// let mut people = table.deserialize::<Person>();
+/// let john = people.next().unwrap().unwrap();
+/// assert_eq!(("John", 20), (john.name.as_str(), john.age));
+/// assert!(people.next().unwrap().is_err());
+/// ```
+pub trait FromRow: Sized {
+    /// Creates a value from the contents of `row`.
+    fn from_row(row: &Row) -> Result<Self, RowError>;
+}
 
-    /// Returns the headers of the table.
-    ///
-    /// This will be empty if the table had no `<th>` tags in its first row. See
-    /// [`Headers`](type.Headers.html) for more.
-    pub fn headers(&self) -> &Headers {
-        &self.headers
-    }
+/// How whitespace within the contents of each table cell is handled.
+///
+/// In either case, whitespace at the start and end of a cell is removed.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Hash)]
+pub enum Whitespace {
+    /// Keep whitespace exactly as it appears in the source. This is the
+    /// default.
+    #[default]
+    Preserve,
+    /// Replace each run of spaces, tabs, and line breaks with a single space,
+    /// as a browser does when rendering the text. Non-breaking spaces are not
+    /// affected.
+    Collapse,
+}
 
-    /// Returns an iterator over the [`Row`](struct.Row.html)s of the table.
-    ///
-    /// Only `<td>` cells are considered when generating rows. If the first row
-    /// of the table is a header row, meaning it contains at least one `<th>`
-    /// cell, the iterator will start on the second row. Use
-    /// [`headers`](#method.headers) to access the header row in that case.
-    pub fn iter(&self) -> Iter {
-        Iter {
-            headers: &self.headers,
-            iter: self.data.iter(),
-        }
-    }
+/// What to put in the extra positions covered by a cell that spans several
+/// rows or columns.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Hash)]
+pub enum SpanFill {
+    /// Repeat the contents of the cell. This is the default.
+    #[default]
+    Duplicate,
+    /// Use an empty string.
+    Empty,
+}
 
-    pub fn new(element: ElementRef) -> Table {
-        let sel_tr = css("tr");
-        let sel_th = css("th");
-        let sel_td = css("td");
+/// Whether a position in a row holds a cell itself or a copy of a cell that
+/// spans several rows or columns, as reported by
+/// [`Cell::span`](struct.Cell.html#method.span).
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum CellSpan {
+    /// The cell starts at this position. This is the case for every cell
+    /// without a `rowspan` or `colspan`.
+    #[default]
+    Origin,
+    /// The position is covered by a cell that starts in an earlier row or
+    /// column.
+    Spanned {
+        /// The number of rows between the cell's first row and this one.
+        row_offset: usize,
+        /// The number of columns between the cell's first column and this
+        /// one.
+        col_offset: usize,
+    },
+}
 
-        let mut headers = HashMap::new();
-        let mut rows = element.select(&sel_tr).peekable();
-        if let Some(tr) = rows.peek() {
-            for (i, th) in tr.select(&sel_th).enumerate() {
-                headers.insert(cell_content(th), i);
-            }
-        }
-        if !headers.is_empty() {
-            rows.next();
-        }
-        let data = rows.map(|tr| select_cells(tr, &sel_td)).collect();
+/// The kind of values found in a column, as inferred by
+/// [`Table::infer_schema`](struct.Table.html#method.infer_schema).
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ColumnType {
+    /// Every non-empty cell parses as an `i64`.
+    Integer,
+    /// Every non-empty cell parses as an `f64`, but not all as integers.
+    Float,
+    /// Every non-empty cell is `true` or `false`.
+    Boolean,
+    /// Anything else, including columns with no non-empty cells.
+    Text,
+}
 
-        Table { headers, data }
-    }
+/// A summary of the tables in some HTML, returned by
+/// [`Table::diagnostics`](struct.Table.html#method.diagnostics).
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Diagnostics {
+    tables: Vec<TableSummary>,
 }
 
-impl<'a> IntoIterator for &'a Table {
-    type Item = Row<'a>;
-    type IntoIter = Iter<'a>;
+impl Diagnostics {
+    /// Returns the number of `<table>` elements, including nested ones.
+    pub fn num_tables(&self) -> usize {
+        self.tables.len()
+    }
 
-    fn into_iter(self) -> Self::IntoIter {
-        self.iter()
+    /// Returns a summary of each `<table>` element, in document order.
+    pub fn tables(&self) -> &[TableSummary] {
+        &self.tables
     }
 }
 
-/// An iterator over the rows in a [`Table`](struct.Table.html).
-pub struct Iter<'a> {
-    headers: &'a Headers,
-    iter: std::slice::Iter<'a, Vec<String>>,
+/// A summary of a `<table>` element, as part of [`Diagnostics`].
+///
+/// [`Diagnostics`]: struct.Diagnostics.html
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct TableSummary {
+    id: Option<String>,
+    header_cells: Vec<String>,
+    num_rows: usize,
+    num_footer_rows: usize,
 }
 
-impl<'a> Iterator for Iter<'a> {
-    type Item = Row<'a>;
+impl TableSummary {
+    /// Returns the `id` attribute of the `<table>` element, if it has one.
+    pub fn id(&self) -> Option<&str> {
+        self.id.as_deref()
+    }
 
-    fn next(&mut self) -> Option<Self::Item> {
-        let headers = self.headers;
-        self.iter.next().map(|cells| Row { headers, cells })
+    /// Returns the text of the `<th>` cells in the first row of the table.
+    ///
+    /// These are the headers compared by
+    /// [`Table::find_by_headers`](struct.Table.html#method.find_by_headers),
+    /// so the table is found if they contain all of the requested headers.
+    pub fn header_cells(&self) -> &[String] {
+        &self.header_cells
+    }
+
+    /// Returns the number of rows in the table, including header and footer
+    /// rows but not the rows of nested tables.
+    pub fn num_rows(&self) -> usize {
+        self.num_rows
+    }
+
+    /// Returns the number of rows in the `<tfoot>`, which are left out of
+    /// [`Table::iter`](struct.Table.html#method.iter).
+    pub fn num_footer_rows(&self) -> usize {
+        self.num_footer_rows
     }
 }
 
-/// A row in a [`Table`](struct.Table.html).
+/// A parsed HTML table.
 ///
-/// A row consists of a number of data cells stored as strings. If the row
-/// contains the same number of cells as the table's header row, its cells can
-/// be safely accessed by header names using [`get`](#method.get). Otherwise,
-/// the data should be accessed via [`as_slice`](#method.as_slice) or by
-/// iterating over the row.
+/// See [the module level documentation](index.html) for more.
 ///
-/// This struct can be thought of as a lightweight reference into a table. As
-/// such, it implements the `Copy` trait.
-#[derive(Clone, Copy, Debug, Eq, PartialEq)]
-pub struct Row<'a> {
-    headers: &'a Headers,
-    cells: &'a [String],
+/// With the `serde` feature enabled, tables implement `Serialize` and
+/// `Deserialize`, so they can be cached without parsing the HTML again.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Table {
+    headers: HeaderInfo,
+    data: Vec<Vec<String>>,
+    footer: Vec<Vec<String>>,
+    caption: Option<String>,
+    /// Information about the cells in `data` followed by those in `footer`.
+    cells: Vec<Vec<Cell>>,
+    /// Information about the `<tr>` element of each row in `data` followed
+    /// by `footer`.
+    rows: Vec<RowInfo>,
+    /// The `id` attribute of the `<table>` element.
+    id: Option<String>,
+    /// The classes of the `<table>` element.
+    classes: Vec<String>,
 }
 
-impl<'a> Row<'a> {
-    /// Returns the number of cells in the row.
-    pub fn len(&self) -> usize {
-        self.cells.len()
-    }
+/// The headers of a table, in the forms needed to look up cells.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+struct HeaderInfo {
+    /// A map from each header to its first column.
+    map: Headers,
+    /// The header names in column order.
+    names: Vec<String>,
+    /// The first column of each header in `names`.
+    columns: Vec<usize>,
+    /// The key in `map` of each header in `names`.
+    keys: Vec<String>,
+    /// The content of each column of the header row, with a header spanning
+    /// several columns repeated in each of them.
+    row: Vec<String>,
+    /// Information about each column of the header row.
+    cells: Vec<Cell>,
+    /// Whether the headers were taken from a header row, rather than from
+    /// `<col>` elements.
+    has_row: bool,
+    /// Whether the keys of `map` were folded with `fold_header`, so that
+    /// lookups must be folded too.
+    ascii_fold: bool,
+}
 
-    /// Returns `true` if the row contains no cells.
-    pub fn is_empty(&self) -> bool {
-        self.cells.is_empty()
+impl HeaderInfo {
+    /// Adds a header named `name` whose first column is `col`.
+    fn push(&mut self, name: String, col: usize) {
+        self.keys.push(name.clone());
+        self.names.push(name);
+        self.columns.push(col);
     }
 
-    /// Returns the cell underneath `header`.
-    ///
-    /// Returns `None` if there is no such header, or if there is no cell at
-    /// that position in the row.
-    pub fn get(&self, header: &str) -> Option<&'a str> {
-        self.headers
-            .get(header)
-            .and_then(|&i| self.cells.get(i).map(String::as_str))
+    /// Returns the headers in column order, along with their first columns.
+    fn list(&self) -> impl Iterator<Item = (&String, usize)> {
+        self.names.iter().zip(self.columns.iter().copied())
     }
 
-    /// Returns a slice containing all the cells.
-    pub fn as_slice(&self) -> &'a [String] {
-        self.cells
+    /// Returns the keys of the headers in column order, along with their
+    /// first columns.
+    fn keyed(&self) -> impl Iterator<Item = (&String, usize)> {
+        self.keys.iter().zip(self.columns.iter().copied())
     }
 
-    /// Returns an iterator over the cells of the row.
-    pub fn iter(&self) -> std::slice::Iter<String> {
-        self.cells.iter()
+    /// Returns `header` as it is compared with the keys, folding it first if
+    /// the keys were.
+    fn key<'h>(&self, header: &'h str) -> Cow<'h, str> {
+        if self.ascii_fold {
+            Cow::Owned(fold_header(header))
+        } else {
+            Cow::Borrowed(header)
+        }
     }
-}
-
-impl<'a> IntoIterator for Row<'a> {
-    type Item = &'a String;
-    type IntoIter = std::slice::Iter<'a, String>;
 
-    fn into_iter(self) -> Self::IntoIter {
-        self.cells.iter()
+    /// Returns the column of `header`, folding it first if the keys were.
+    fn index(&self, header: &str) -> Option<usize> {
+        self.map.get(self.key(header).as_ref()).copied()
     }
 }
 
-fn css(selector: &'static str) -> Selector {
-    Selector::parse(selector).unwrap()
+/// Information about the `<tr>` element of a row.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+struct RowInfo {
+    /// The zero-based position of the row among all the `<tr>` elements of
+    /// the table.
+    source: usize,
+    /// The `id` attribute of the `<tr>` element.
+    id: Option<String>,
+    /// The classes of the `<tr>` element.
+    classes: Vec<String>,
+    /// The `data-*` attributes of the `<tr>` element, if they were requested
+    /// with `TableBuilder::row_data_attrs`.
+    data_attrs: Vec<(String, String)>,
 }
 
-fn select_cells(element: ElementRef, selector: &Selector) -> Vec<String> {
-    element.select(selector).map(cell_content).collect()
-}
+/// The row information of rows that do not come from a `<tr>` element.
+static NO_ROW: RowInfo = RowInfo {
+    source: 0,
+    id: None,
+    classes: Vec::new(),
+    data_attrs: Vec::new(),
+};
 
-fn cell_content(element: ElementRef) -> String {
-    element.inner_html().trim().to_string()
+impl RowInfo {
+    fn new(source: usize, tr: ElementRef, options: &TableBuilder) -> RowInfo {
+        let data_attrs = if options.row_data_attrs {
+            let attrs = tr.value().attrs();
+            let data = attrs.filter(|(name, _)| name.starts_with("data-"));
+            data.map(|(name, value)| (name.to_string(), value.to_string()))
+                .collect()
+        } else {
+            Vec::new()
+        };
+        RowInfo {
+            source,
+            id: tr.value().id().map(String::from),
+            classes: class_list(tr),
+            data_attrs,
+        }
+    }
 }
 
-fn contains_str(slice: &[String], item: &str) -> bool {
-    slice.iter().any(|s| s == item)
+/// Information about a table cell beyond its content.
+///
+/// Each cell's contents are stored both as HTML and as text, regardless of the
+/// [`TextMode`](enum.TextMode.html) used to parse the table, so a table uses
+/// roughly twice as much memory per cell as its contents alone would require.
+/// The cell's attributes are stored as well, which can double that again for
+/// cells with many attributes.
+///
+/// Positions filled by a cell spanning several rows or columns have a copy of
+/// its information, unless the table was parsed with
+/// [`SpanFill::Empty`](enum.SpanFill.html#variant.Empty).
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Cell {
+    html: String,
+    text: String,
+    links: Vec<String>,
+    attrs: HashMap<String, String>,
+    header: bool,
+    span: CellSpan,
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+impl Cell {
+    fn new(element: ElementRef, options: &TableBuilder) -> Cell {
+        let links = element
+            .select(&SEL_LINK)
+            .filter_map(|a| a.value().attr("href"))
+            .map(String::from)
+            .collect();
+        Cell {
+            html: options.extract(element, TextMode::Html),
+            text: options.extract(element, TextMode::Text),
+            links,
+            attrs: element
+                .value()
+                .attrs()
+                .map(|(name, value)| (name.to_string(), value.to_string()))
+                .collect(),
+            header: element.value().name() == "th",
+            span: CellSpan::Origin,
+        }
+    }
 
-    const TABLE_EMPTY: &'static str = r#"
-<table></table>
-"#;
+    /// Returns the inner HTML of the cell, as with
+    /// [`TextMode::Html`](enum.TextMode.html#variant.Html).
+    pub fn html(&self) -> &str {
+        &self.html
+    }
 
-    const TABLE_TH: &'static str = r#"
-<table>
-    <tr><th>Name</th><th>Age</th></tr>
-</table>
-"#;
+    /// Returns the text of the cell, as with
+    /// [`TextMode::Text`](enum.TextMode.html#variant.Text).
+    pub fn text(&self) -> &str {
+        &self.text
+    }
 
-    const TABLE_TD: &'static str = r#"
-<table>
-    <tr><td>Name</td><td>Age</td></tr>
-</table>
-"#;
+    /// Returns the `href` targets of the links in the cell, in document order.
+    pub fn links(&self) -> Vec<&str> {
+        self.links.iter().map(String::as_str).collect()
+    }
 
-    const TABLE_TH_TD: &'static str = r#"
-<table>
-    <tr><th>Name</th><th>Age</th></tr>
-    <tr><td>John</td><td>20</td></tr>
-</table>
-"#;
+    /// Returns the value of the cell's attribute `name`, such as
+    /// `data-sort-value`, if it has one.
+    pub fn attr(&self, name: &str) -> Option<&str> {
+        self.attrs.get(name).map(String::as_str)
+    }
 
-    const TABLE_TD_TD: &'static str = r#"
-<table>
-    <tr><td>Name</td><td>Age</td></tr>
-    <tr><td>John</td><td>20</td></tr>
-</table>
-"#;
+    /// Returns all the attributes of the cell, mapped from name to value.
+    pub fn attrs(&self) -> &HashMap<String, String> {
+        &self.attrs
+    }
 
-    const TABLE_TH_TH: &'static str = r#"
-<table>
-    <tr><th>Name</th><th>Age</th></tr>
-    <tr><th>John</th><th>20</th></tr>
-</table>
-"#;
+    /// Returns whether the cell starts at this position or spans into it from
+    /// an earlier row or column.
+    ///
+    /// This makes it possible to skip the copies of a cell with a `rowspan`
+    /// or `colspan`, for example to avoid counting a value twice when summing
+    /// a column.
+    pub fn span(&self) -> CellSpan {
+        self.span
+    }
 
-    const TABLE_COMPLEX: &'static str = r#"
-<table>
-    <tr><th>Name</th><th>Age</th><th>Extra</th></tr>
-    <tr><td>John</td><td>20</td></tr>
-    <tr><td>May</td><td>30</td><td>foo</td></tr>
-    <tr></tr>
-    <tr><td>a</td><td>b</td><td>c</td><td>d</td></tr>
-</table>
-"#;
+    /// Returns true if the cell starts at this position, rather than
+    /// spanning into it from an earlier row or column.
+    pub fn is_origin(&self) -> bool {
+        self.span == CellSpan::Origin
+    }
 
-    const HTML_NO_TABLE: &'static str = r#"
-<!doctype HTML>
-<html>
-    <head><title>foo</title></head>
-    <body><p>Hi.</p></body>
-</html>
-"#;
+    /// Returns true if the cell is a `<th>` rather than a `<td>`.
+    pub fn is_header(&self) -> bool {
+        self.header
+    }
+}
 
-    const HTML_TWO_TABLES: &'static str = r#"
-<!doctype HTML>
-<html>
-    <head><title>foo</title></head>
-    <body>
-        <table id="first">
-            <tr><th>Name</th><th>Age</th></tr>
-            <tr><td>John</td><td>20</td></tr>
-        </table>
-        <table id="second">
-            <tr><th>Name</th><th>Weight</th></tr>
-            <tr><td>John</td><td>150</td></tr>
-        </table>
-    </body>
-</html>
-"#;
+impl Table {
+    /// Finds the first table in `html`.
+    ///
+    /// The HTML is parsed as a fragment, which accepts snippets and incomplete
+    /// markup as well as whole documents. See
+    /// [`find_first_from_document`](#method.find_first_from_document) for
+    /// complete documents.
+    ///
+    /// Tables inside `<template>` elements are found like any others, since
+    /// the parser keeps the template contents in the tree. Shadow roots
+    /// attached by scripts are not part of the HTML, so tables in them cannot
+    /// be found; declarative shadow roots written as
+    /// `<template shadowrootmode="open">` are ordinary templates here.
+    pub fn find_first(html: &str) -> Option<Table> {
+        Table::try_find_first(html).ok()
+    }
 
-    const HTML_TABLE_FRAGMENT: &'static str = r#"
-        <table id="first">
-            <tr><th>Name</th><th>Age</th></tr>
-            <tr><td>John</td><td>20</td></tr>
-        </table>
-    </body>
-</html>
-"#;
+    /// Like [`find_first`](#method.find_first), but returns an error
+    /// explaining why no table was found.
+    pub fn try_find_first(html: &str) -> Result<Table, TableError> {
+        let html = Html::parse_fragment(html);
+        Table::find_first_from_html(&html).ok_or(TableError::NoTableFound)
+    }
 
-    #[test]
-    fn test_find_first_none() {
-        assert_eq!(None, Table::find_first(""));
-        assert_eq!(None, Table::find_first("foo"));
-        assert_eq!(None, Table::find_first(HTML_NO_TABLE));
+    /// Like [`find_first`](#method.find_first), but parses `html` as a complete
+    /// document rather than a fragment.
+    ///
+    /// Prefer this for whole pages starting with `<!DOCTYPE html>` or
+    /// `<html>`. Document parsing follows the same rules as a browser loading
+    /// the page, including the handling of `<html>`, `<head>`, and `<body>`,
+    /// so the result matches what the browser shows. Prefer
+    /// [`find_first`](#method.find_first) for snippets of HTML, such as a
+    /// table on its own or markup cut out of a larger page.
+    pub fn find_first_from_document(html: &str) -> Option<Table> {
+        let html = Html::parse_document(html);
+        Table::find_first_from_html(&html)
     }
 
-    #[test]
-    fn test_find_first_empty() {
-        let empty = Table {
-            headers: HashMap::new(),
-            data: Vec::new(),
-        };
-        assert_eq!(Some(empty), Table::find_first(TABLE_EMPTY));
+    /// Like [`find_first`](#method.find_first), but skips tables that look like
+    /// they are used for page layout rather than holding data.
+    ///
+    /// By default, tables with fewer than 2 rows are skipped, as are tables
+    /// without `<th>` cells that have only one column. Use a
+    /// [`DataTableFilter`](struct.DataTableFilter.html) to change these
+    /// thresholds.
+    ///
+    /// ```
+    /// # use table_extract::Table;
+    /// let html = r#"
+    ///     <table><tr><td>
+    ///         <p>Welcome!</p>
+    ///         <table>
+    ///             <tr><th>Name</th><th>Age</th></tr>
+    ///             <tr><td>John</td><td>20</td></tr>
+    ///         </table>
+    ///     </td></tr></table>
+    /// "#;
+    /// let table = Table::find_first_data_table(html).unwrap();
+    /// assert_eq!(vec!["Name", "Age"], table.header_names());
+    /// ```
+    pub fn find_first_data_table(html: &str) -> Option<Table> {
+        DataTableFilter::new().find_first(html)
     }
 
-    #[test]
-    fn test_find_first_some() {
-        assert!(Table::find_first(TABLE_TH).is_some());
-        assert!(Table::find_first(TABLE_TD).is_some());
+    /// Summarizes the tables in `html`, to help find out why a function like
+    /// [`find_by_headers`](#method.find_by_headers) found no table.
+    ///
+    /// This reports each `<table>` element, including nested ones, with the
+    /// `<th>` cells of its first row and the number of rows it has.
+    ///
+    /// ```
+    /// # use table_extract::Table;
+    /// let html = r#"
+    ///     <table id="people">
+    ///         <tr><th>Name</th><th>Age </th></tr>
+    ///         <tr><td>John</td><td>20</td></tr>
+    ///     </table>
+    /// "#;
+    /// assert_eq!(None, Table::find_by_headers(html, &["Name", "Age "]));
+    /// let diagnostics = Table::diagnostics(html);
+    /// assert_eq!(1, diagnostics.num_tables());
+    /// let summary = &diagnostics.tables()[0];
+    /// assert_eq!(Some("people"), summary.id());
+    /// assert_eq!(&["Name", "Age"], summary.header_cells());
+    /// assert_eq!(2, summary.num_rows());
+    /// ```
+    pub fn diagnostics(html: &str) -> Diagnostics {
+        let html = Html::parse_fragment(html);
+        let tables = html.root_element().select(&SEL_TABLE).map(|table| {
+            let rows: Vec<_> = table
+                .select(&SEL_TR)
+                .filter(|&tr| !is_nested(tr, table))
+                .collect();
+            TableSummary {
+                id: table.value().id().map(String::from),
+                header_cells: rows
+                    .first()
+                    .map_or_else(Vec::new, |&tr| select_cells(tr, &SEL_TH)),
+                num_rows: rows.len(),
+                num_footer_rows: rows
+                    .iter()
+                    .filter(|&&tr| parent_is(tr, "tfoot"))
+                    .count(),
+            }
+        });
+        Diagnostics {
+            tables: tables.collect(),
+        }
+    }
+
+    /// Reads HTML from `reader` to the end and finds the first table in it.
+    ///
+    /// Errors from reading are returned as they are, and invalid UTF-8 results
+    /// in an error of kind `InvalidData`. If the HTML was read successfully but
+    /// contains no table, this returns `Ok(None)`.
+    pub fn find_first_from_reader<R: Read>(
+        mut reader: R,
+    ) -> io::Result<Option<Table>> {
+        let mut html = String::new();
+        reader.read_to_string(&mut html)?;
+        Ok(Table::find_first(&html))
+    }
+
+    /// Finds the first table in `html`, extracting the contents of each cell
+    /// with `f` instead of taking its inner HTML.
+    ///
+    /// The closure is also applied to header cells and the caption, and its
+    /// result is used as it is, without trimming. Information such as
+    /// [`Row::get_text`](struct.Row.html#method.get_text) and
+    /// [`Row::get_links`](struct.Row.html#method.get_links) is unaffected.
+    ///
+    /// ```
+    /// # use table_extract::Table;
+    /// let html = r#"
+    /// <table>
+    ///     <tr><th>Name</th><th>Price</th></tr>
+    ///     <tr><td>Tea</td><td data-value="2.50">$2.50</td></tr>
+    /// </table>
+    /// "#;
+    /// let table = Table::find_first_with(html, |cell| {
+    ///     match cell.value().attr("data-value") {
+    ///         Some(value) => value.to_string(),
+    ///         None => cell.text().collect(),
+    ///     }
+    /// })
+    /// .unwrap();
+    /// let row = table.iter().next().unwrap();
+    /// assert_eq!(Some("2.50"), row.get("Price"));
+    /// ```
+    pub fn find_first_with<F>(html: &str, f: F) -> Option<Table>
+    where
+        F: Fn(ElementRef) -> String,
+    {
+        let html = Html::parse_fragment(html);
+        let table = html.root_element().select(&SEL_TABLE).next()?;
+        Some(TableBuilder::new().build_with(table, f))
+    }
+
+    /// Finds the first table in `html` and calls `f` with each of the rows that
+    /// [`iter`](#method.iter) would cover, in order.
+    ///
+    /// Each row is laid out just before it is passed to `f` and dropped
+    /// afterwards, so the contents of the table are never held all at once.
+    /// This suits large tables that only need to be read once. The HTML itself
+    /// is still parsed in full first. Does nothing if there is no table.
+    ///
+    /// ```
+    /// # use table_extract::Table;
+    /// let html = r#"
+    /// <table>
+    ///     <tr><th>Name</th><th>Age</th></tr>
+    ///     <tr><td>John</td><td>20</td></tr>
+    ///     <tr><td>May</td><td>30</td></tr>
+    /// </table>
+    /// "#;
+    /// let mut total = 0;
+    /// Table::for_each_row(html, |row| {
+    ///     total += row.get("Age").unwrap().parse::<u32>().unwrap();
+    /// });
+    /// assert_eq!(50, total);
+    /// ```
+    pub fn for_each_row<F>(html: &str, f: F)
+    where
+        F: FnMut(Row<'_>),
+    {
+        TableBuilder::new().for_each_row(html, f)
+    }
+
+    /// Finds the first table in a parsed HTML document or fragment.
+    pub fn find_first_from_html(html: &Html) -> Option<Table> {
+        Table::find_first_from_elem(&html.root_element())
+    }
+
+    /// Finds the first table that is a descendant of `element`.
+    pub fn find_first_from_elem(element: &ElementRef) -> Option<Table> {
+        element.select(&SEL_TABLE).next().map(Table::new)
+    }
+
+    /// Finds all tables in `html`, in document order.
+    pub fn find_all(html: &str) -> Vec<Table> {
+        let html = Html::parse_fragment(html);
+        Table::find_all_from_html(&html)
+    }
+
+    /// Finds all tables in a parsed HTML document or fragment, in document
+    /// order.
+    pub fn find_all_from_html(html: &Html) -> Vec<Table> {
+        Table::find_all_from_elem(&html.root_element())
+    }
+
+    /// Finds all tables that are descendants of `element`, in document order.
+    pub fn find_all_from_elem(element: &ElementRef) -> Vec<Table> {
+        element.select(&SEL_TABLE).map(Table::new).collect()
+    }
+
+    /// Like [`find_all`](#method.find_all), but parses the tables concurrently
+    /// using [rayon](https://docs.rs/rayon). Requires the `rayon` feature.
+    ///
+    /// Because scraper's elements cannot be shared between threads, the tables
+    /// are first selected and serialized back to HTML, and each one is then
+    /// parsed again on its own thread. On a single thread, this extra work
+    /// makes it take about 1.5 times as long as `find_all` regardless of the
+    /// size of the tables, so it only pays off with at least two cores and
+    /// enough tables to keep them busy. The `parallel` example measures the
+    /// difference on a particular machine:
+    ///
+    /// ```text
+    /// cargo run --release --features rayon --example parallel
+    /// ```
+    ///
+    /// A table nested inside another is serialized along with it, so nested
+    /// tables are parsed more than once.
+    #[cfg(feature = "rayon")]
+    pub fn find_all_parallel(html: &str) -> Vec<Table> {
+        use rayon::prelude::*;
+
+        let html = Html::parse_fragment(html);
+        let tables: Vec<String> = html
+            .root_element()
+            .select(&SEL_TABLE)
+            .map(|table| table.html())
+            .collect();
+        tables
+            .par_iter()
+            .filter_map(|table| Table::find_first(table))
+            .collect()
+    }
+
+    /// Finds the table at the zero-based position `n` among all the tables in
+    /// `html`, in document order.
+    ///
+    /// Tables are counted in the same way as [`find_all`](#method.find_all),
+    /// so nested tables are included. Returns `None` if there are not enough
+    /// tables.
+    pub fn find_nth(html: &str, n: usize) -> Option<Table> {
+        let html = Html::parse_fragment(html);
+        Table::find_nth_from_html(&html, n)
+    }
+
+    /// Finds the table at the zero-based position `n` among all the tables in
+    /// a parsed HTML document or fragment.
+    pub fn find_nth_from_html(html: &Html, n: usize) -> Option<Table> {
+        Table::find_nth_from_elem(&html.root_element(), n)
+    }
+
+    /// Finds the table at the zero-based position `n` among all the tables
+    /// that are descendants of `element`.
+    pub fn find_nth_from_elem(element: &ElementRef, n: usize) -> Option<Table> {
+        element.select(&SEL_TABLE).nth(n).map(Table::new)
+    }
+
+    /// Finds the table in `html` with an id of `id`.
+    ///
+    /// The id is matched literally, even if it contains characters such as
+    /// spaces, dots, or colons that have special meaning in CSS.
+    pub fn find_by_id(html: &str, id: &str) -> Option<Table> {
+        Table::try_find_by_id(html, id).ok()
+    }
+
+    /// Like [`find_by_id`](#method.find_by_id), but returns an error
+    /// explaining why no table was found.
+    pub fn try_find_by_id(html: &str, id: &str) -> Result<Table, TableError> {
+        let html = Html::parse_fragment(html);
+        select_by_id(&html.root_element(), id).map(Table::new)
+    }
+
+    /// Finds the table with an id of `id` in a parsed HTML document or
+    /// fragment.
+    pub fn find_by_id_from_html(html: &Html, id: &str) -> Option<Table> {
+        Table::find_by_id_from_elem(&html.root_element(), id)
+    }
+
+    /// Finds the table with an id of `id` that is a descendant of `element`.
+    pub fn find_by_id_from_elem(
+        element: &ElementRef,
+        id: &str,
+    ) -> Option<Table> {
+        select_by_id(element, id).ok().map(Table::new)
+    }
+
+    /// Finds the first table in `html` with a class of `class`.
+    ///
+    /// The class is compared literally rather than being interpreted as a CSS
+    /// selector, so a class containing whitespace or other characters that are
+    /// not valid in CSS identifiers simply matches nothing.
+    pub fn find_by_class(html: &str, class: &str) -> Option<Table> {
+        let html = Html::parse_fragment(html);
+        Table::find_by_class_from_html(&html, class)
+    }
+
+    /// Finds the first table with a class of `class` in a parsed HTML document
+    /// or fragment.
+    ///
+    /// See [`find_by_class`](#method.find_by_class) for more.
+    pub fn find_by_class_from_html(html: &Html, class: &str) -> Option<Table> {
+        Table::find_by_class_from_elem(&html.root_element(), class)
+    }
+
+    /// Finds the first table with a class of `class` that is a descendant of
+    /// `element`.
+    ///
+    /// See [`find_by_class`](#method.find_by_class) for more.
+    pub fn find_by_class_from_elem(
+        element: &ElementRef,
+        class: &str,
+    ) -> Option<Table> {
+        select_by_class(element, class).map(Table::new)
+    }
+
+    /// Finds the first element in `html` matching the CSS selector `selector`,
+    /// and parses it as a table.
+    ///
+    /// Returns `None` if `selector` is not a valid CSS selector or if nothing
+    /// matches it. The matched element should normally be a `<table>`;
+    /// otherwise its descendant rows are treated as the rows of a table.
+    pub fn find_by_selector(html: &str, selector: &str) -> Option<Table> {
+        Table::try_find_by_selector(html, selector).ok()
+    }
+
+    /// Like [`find_by_selector`](#method.find_by_selector), but returns an
+    /// error explaining why no table was found, which distinguishes an invalid
+    /// selector from one that matches nothing.
+    ///
+    /// ```
+    /// # use table_extract::{Table, TableError};
+    /// let html = "<table><tr><td>1</td></tr></table>";
+    /// assert_eq!(
+    ///     Err(TableError::InvalidSelector("table[".to_string())),
+    ///     Table::try_find_by_selector(html, "table[")
+    /// );
+    /// assert_eq!(
+    ///     Err(TableError::NoTableFound),
+    ///     Table::try_find_by_selector(html, "table.missing")
+    /// );
+    /// ```
+    pub fn try_find_by_selector(
+        html: &str,
+        selector: &str,
+    ) -> Result<Table, TableError> {
+        let parsed = Selector::parse(selector)
+            .map_err(|_| TableError::InvalidSelector(selector.to_string()))?;
+        let html = Html::parse_fragment(html);
+        Table::find_by_selector_from_html(&html, &parsed)
+            .ok_or(TableError::NoTableFound)
+    }
+
+    /// Finds the first element matching `selector` in a parsed HTML document
+    /// or fragment, and parses it as a table.
+    ///
+    /// See [`find_by_selector`](#method.find_by_selector) for more.
+    pub fn find_by_selector_from_html(
+        html: &Html,
+        selector: &Selector,
+    ) -> Option<Table> {
+        Table::find_by_selector_from_elem(&html.root_element(), selector)
+    }
+
+    /// Finds the first descendant of `element` matching `selector`, and parses
+    /// it as a table.
+    ///
+    /// See [`find_by_selector`](#method.find_by_selector) for more.
+    pub fn find_by_selector_from_elem(
+        element: &ElementRef,
+        selector: &Selector,
+    ) -> Option<Table> {
+        element.select(selector).next().map(Table::new)
+    }
+
+    /// Finds the first element matching the CSS selector `table` within an
+    /// element matching the CSS selector `ancestor`, and parses it as a table.
+    ///
+    /// The elements matching `ancestor` are searched in document order, so the
+    /// table comes from the first of them that has one. Returns `None` if
+    /// either selector is invalid, or if no match for `table` is found within a
+    /// match for `ancestor`.
+    ///
+    /// ```
+    /// # use table_extract::Table;
+    /// let html = r#"
+    ///     <div id="sidebar"><table><tr><td>Menu</td></tr></table></div>
+    ///     <div id="main">
+    ///         <table>
+    ///             <tr><th>Name</th></tr>
+    ///             <tr><td>John</td></tr>
+    ///         </table>
+    ///     </div>
+    /// "#;
+    /// let table = Table::find_first_under(html, "div#main", "table").unwrap();
+    /// assert_eq!(Some("John"), table.iter().next().unwrap().get("Name"));
+    /// ```
+    pub fn find_first_under(
+        html: &str,
+        ancestor: &str,
+        table: &str,
+    ) -> Option<Table> {
+        let ancestor = Selector::parse(ancestor).ok()?;
+        let table = Selector::parse(table).ok()?;
+        let html = Html::parse_fragment(html);
+        let mut ancestors = html.root_element().select(&ancestor);
+        ancestors.find_map(|e| Table::find_by_selector_from_elem(&e, &table))
+    }
+
+    /// Finds the table in `html` whose first row contains all of the headers
+    /// specified in `headers`. The order does not matter.
+    ///
+    /// If `headers` is empty, this is the same as
+    /// [`find_first`](#method.find_first).
+    pub fn find_by_headers<T>(html: &str, headers: &[T]) -> Option<Table>
+    where
+        T: AsRef<str>,
+    {
+        let html = Html::parse_fragment(html);
+        Table::find_by_headers_from_html(&html, headers)
+    }
+
+    /// Finds the table in a parsed HTML document or fragment whose first row
+    /// contains all of the headers specified in `headers`.
+    ///
+    /// See [`find_by_headers`](#method.find_by_headers) for more.
+    pub fn find_by_headers_from_html<T>(
+        html: &Html,
+        headers: &[T],
+    ) -> Option<Table>
+    where
+        T: AsRef<str>,
+    {
+        Table::find_by_headers_from_elem(&html.root_element(), headers)
+    }
+
+    /// Finds the table that is a descendant of `element` and whose first row
+    /// contains all of the headers specified in `headers`.
+    ///
+    /// See [`find_by_headers`](#method.find_by_headers) for more.
+    pub fn find_by_headers_from_elem<T>(
+        element: &ElementRef,
+        headers: &[T],
+    ) -> Option<Table>
+    where
+        T: AsRef<str>,
+    {
+        select_by_headers(element, headers).map(|(_, e)| Table::new(e))
+    }
+
+    /// Finds the table in `html` whose first row contains exactly the headers
+    /// specified in `headers`, and no others. The order does not matter.
+    ///
+    /// Unlike [`find_by_headers`](#method.find_by_headers), this skips tables
+    /// with extra headers, so it does not pick a broader table that happens to
+    /// share the requested columns. If `headers` is empty, this finds the
+    /// first table whose first row has no `<th>` cells.
+    ///
+    /// ```
+    /// # use table_extract::Table;
+    /// let html = r#"
+    /// <table id="wide"><tr><th>Name</th><th>Age</th><th>City</th></tr></table>
+    /// <table id="narrow"><tr><th>Age</th><th>Name</th></tr></table>
+    /// "#;
+    /// let table = Table::find_by_exact_headers(html, &["Name", "Age"]).unwrap();
+    /// assert_eq!(Some("narrow"), table.id());
+    /// ```
+    pub fn find_by_exact_headers<T>(html: &str, headers: &[T]) -> Option<Table>
+    where
+        T: AsRef<str>,
+    {
+        let html = Html::parse_fragment(html);
+        Table::find_by_exact_headers_from_html(&html, headers)
+    }
+
+    /// Finds the table in a parsed HTML document or fragment whose first row
+    /// contains exactly the headers specified in `headers`.
+    ///
+    /// See [`find_by_exact_headers`](#method.find_by_exact_headers) for more.
+    pub fn find_by_exact_headers_from_html<T>(
+        html: &Html,
+        headers: &[T],
+    ) -> Option<Table>
+    where
+        T: AsRef<str>,
+    {
+        Table::find_by_exact_headers_from_elem(&html.root_element(), headers)
+    }
+
+    /// Finds the table that is a descendant of `element` and whose first row
+    /// contains exactly the headers specified in `headers`.
+    ///
+    /// See [`find_by_exact_headers`](#method.find_by_exact_headers) for more.
+    pub fn find_by_exact_headers_from_elem<T>(
+        element: &ElementRef,
+        headers: &[T],
+    ) -> Option<Table>
+    where
+        T: AsRef<str>,
+    {
+        select_by_header_cells(element, |cells| {
+            cells.len() == headers.len()
+                && headers.iter().all(|h| contains_str(cells, h.as_ref()))
+                && cells
+                    .iter()
+                    .all(|c| headers.iter().any(|h| h.as_ref() == c))
+        })
+        .map(|(_, e)| Table::new(e))
+    }
+
+    /// Like [`find_by_headers`](#method.find_by_headers), but also returns the
+    /// zero-based position of the table among all the tables in `html`, in
+    /// document order.
+    ///
+    /// The position counts tables in the same way as
+    /// [`find_nth`](#method.find_nth), so it can be passed to that function to
+    /// find the same table again.
+    pub fn find_by_headers_indexed<T>(
+        html: &str,
+        headers: &[T],
+    ) -> Option<(usize, Table)>
+    where
+        T: AsRef<str>,
+    {
+        let html = Html::parse_fragment(html);
+        Table::find_by_headers_indexed_from_html(&html, headers)
+    }
+
+    /// Like [`find_by_headers_from_html`](#method.find_by_headers_from_html),
+    /// but also returns the zero-based position of the table among all the
+    /// tables in `html`.
+    pub fn find_by_headers_indexed_from_html<T>(
+        html: &Html,
+        headers: &[T],
+    ) -> Option<(usize, Table)>
+    where
+        T: AsRef<str>,
+    {
+        Table::find_by_headers_indexed_from_elem(&html.root_element(), headers)
+    }
+
+    /// Like [`find_by_headers_from_elem`](#method.find_by_headers_from_elem),
+    /// but also returns the zero-based position of the table among all the
+    /// tables that are descendants of `element`.
+    pub fn find_by_headers_indexed_from_elem<T>(
+        element: &ElementRef,
+        headers: &[T],
+    ) -> Option<(usize, Table)>
+    where
+        T: AsRef<str>,
+    {
+        let table = select_by_headers(element, headers);
+        table.map(|(i, e)| (i, Table::new(e)))
+    }
+
+    /// Like [`find_by_headers`](#method.find_by_headers), but compares headers
+    /// case-insensitively.
+    pub fn find_by_headers_ci<T>(html: &str, headers: &[T]) -> Option<Table>
+    where
+        T: AsRef<str>,
+    {
+        let html = Html::parse_fragment(html);
+        Table::find_by_headers_ci_from_html(&html, headers)
+    }
+
+    /// Like [`find_by_headers_from_html`](#method.find_by_headers_from_html),
+    /// but compares headers case-insensitively.
+    pub fn find_by_headers_ci_from_html<T>(
+        html: &Html,
+        headers: &[T],
+    ) -> Option<Table>
+    where
+        T: AsRef<str>,
+    {
+        Table::find_by_headers_ci_from_elem(&html.root_element(), headers)
+    }
+
+    /// Like [`find_by_headers_from_elem`](#method.find_by_headers_from_elem),
+    /// but compares headers case-insensitively.
+    pub fn find_by_headers_ci_from_elem<T>(
+        element: &ElementRef,
+        headers: &[T],
+    ) -> Option<Table>
+    where
+        T: AsRef<str>,
+    {
+        if headers.is_empty() {
+            return Table::find_first_from_elem(element);
+        }
+        select_by_header_cells(element, |cells| {
+            let cells: Vec<_> =
+                cells.iter().map(|s| s.to_lowercase()).collect();
+            headers
+                .iter()
+                .all(|h| contains_str(&cells, &h.as_ref().to_lowercase()))
+        })
+        .map(|(_, e)| Table::new(e))
+    }
+
+    /// Like [`find_by_headers`](#method.find_by_headers), but each of
+    /// `headers` only needs to be a substring of a header in the table.
+    ///
+    /// This tolerates headers with units or footnote markers appended, such as
+    /// `Age (years)` when searching for `Age`.
+    pub fn find_by_headers_contains<T>(
+        html: &str,
+        headers: &[T],
+    ) -> Option<Table>
+    where
+        T: AsRef<str>,
+    {
+        let html = Html::parse_fragment(html);
+        Table::find_by_headers_contains_from_html(&html, headers)
+    }
+
+    /// Like [`find_by_headers_from_html`](#method.find_by_headers_from_html),
+    /// but each of `headers` only needs to be a substring of a header in the
+    /// table.
+    pub fn find_by_headers_contains_from_html<T>(
+        html: &Html,
+        headers: &[T],
+    ) -> Option<Table>
+    where
+        T: AsRef<str>,
+    {
+        Table::find_by_headers_contains_from_elem(&html.root_element(), headers)
+    }
+
+    /// Like [`find_by_headers_from_elem`](#method.find_by_headers_from_elem),
+    /// but each of `headers` only needs to be a substring of a header in the
+    /// table.
+    pub fn find_by_headers_contains_from_elem<T>(
+        element: &ElementRef,
+        headers: &[T],
+    ) -> Option<Table>
+    where
+        T: AsRef<str>,
+    {
+        if headers.is_empty() {
+            return Table::find_first_from_elem(element);
+        }
+        select_by_header_cells(element, |cells| {
+            headers
+                .iter()
+                .all(|h| cells.iter().any(|cell| cell.contains(h.as_ref())))
+        })
+        .map(|(_, e)| Table::new(e))
+    }
+
+    /// Finds the first table in `html` whose first row contains at least one
+    /// of the headers specified in `headers`.
+    ///
+    /// This suits pages whose columns have been renamed over time. Unlike
+    /// [`find_by_headers`](#method.find_by_headers), this returns `None` if
+    /// `headers` is empty.
+    ///
+    /// ```
+    /// # use table_extract::Table;
+    /// let html = r#"
+    /// <table>
+    ///     <tr><th>Name</th><th>Years</th></tr>
+    ///     <tr><td>John</td><td>20</td></tr>
+    /// </table>
+    /// "#;
+    /// let table = Table::find_by_any_header(html, &["Age", "Years"]).unwrap();
+    /// assert_eq!(vec!["Name", "Years"], table.header_names());
+    /// ```
+    pub fn find_by_any_header<T>(html: &str, headers: &[T]) -> Option<Table>
+    where
+        T: AsRef<str>,
+    {
+        let html = Html::parse_fragment(html);
+        Table::find_by_any_header_from_html(&html, headers)
+    }
+
+    /// Finds the first table in a parsed HTML document or fragment whose first
+    /// row contains at least one of the headers specified in `headers`.
+    ///
+    /// See [`find_by_any_header`](#method.find_by_any_header) for more.
+    pub fn find_by_any_header_from_html<T>(
+        html: &Html,
+        headers: &[T],
+    ) -> Option<Table>
+    where
+        T: AsRef<str>,
+    {
+        Table::find_by_any_header_from_elem(&html.root_element(), headers)
+    }
+
+    /// Finds the first table that is a descendant of `element` and whose first
+    /// row contains at least one of the headers specified in `headers`.
+    ///
+    /// See [`find_by_any_header`](#method.find_by_any_header) for more.
+    pub fn find_by_any_header_from_elem<T>(
+        element: &ElementRef,
+        headers: &[T],
+    ) -> Option<Table>
+    where
+        T: AsRef<str>,
+    {
+        select_by_header_cells(element, |cells| {
+            headers.iter().any(|h| contains_str(cells, h.as_ref()))
+        })
+        .map(|(_, e)| Table::new(e))
+    }
+
+    /// Finds the first table in `html` with a cell whose text is `value`.
+    ///
+    /// Both header and data cells are searched. The text of each cell is
+    /// trimmed and has its whitespace collapsed before comparing, but is
+    /// otherwise matched exactly. This suits tables without ids or stable
+    /// headers that reliably contain a marker string.
+    ///
+    /// ```
+    /// # use table_extract::Table;
+    /// let html = r#"
+    /// <table><tr><td>Weather</td></tr></table>
+    /// <table><tr><td>Quarterly <b>results</b></td><td>42</td></tr></table>
+    /// "#;
+    /// let table = Table::find_containing(html, "Quarterly results").unwrap();
+    /// assert_eq!(Some("42"), table.cell(0, 1));
+    /// ```
+    pub fn find_containing(html: &str, value: &str) -> Option<Table> {
+        let html = Html::parse_fragment(html);
+        Table::find_containing_from_html(&html, value)
+    }
+
+    /// Finds the first table in a parsed HTML document or fragment with a
+    /// cell whose text is `value`.
+    ///
+    /// See [`find_containing`](#method.find_containing) for more.
+    pub fn find_containing_from_html(
+        html: &Html,
+        value: &str,
+    ) -> Option<Table> {
+        Table::find_containing_from_elem(&html.root_element(), value)
+    }
+
+    /// Finds the first table that is a descendant of `element` with a cell
+    /// whose text is `value`.
+    ///
+    /// See [`find_containing`](#method.find_containing) for more.
+    pub fn find_containing_from_elem(
+        element: &ElementRef,
+        value: &str,
+    ) -> Option<Table> {
+        select_by_cell(element, |text| text == value).map(Table::new)
+    }
+
+    /// Like [`find_containing`](#method.find_containing), but `value` only
+    /// needs to be a substring of the text of a cell.
+    pub fn find_containing_substr(html: &str, value: &str) -> Option<Table> {
+        let html = Html::parse_fragment(html);
+        Table::find_containing_substr_from_html(&html, value)
+    }
+
+    /// Like [`find_containing_from_html`](#method.find_containing_from_html),
+    /// but `value` only needs to be a substring of the text of a cell.
+    pub fn find_containing_substr_from_html(
+        html: &Html,
+        value: &str,
+    ) -> Option<Table> {
+        Table::find_containing_substr_from_elem(&html.root_element(), value)
+    }
+
+    /// Like [`find_containing_from_elem`](#method.find_containing_from_elem),
+    /// but `value` only needs to be a substring of the text of a cell.
+    pub fn find_containing_substr_from_elem(
+        element: &ElementRef,
+        value: &str,
+    ) -> Option<Table> {
+        select_by_cell(element, |text| text.contains(value)).map(Table::new)
+    }
+
+    /// Returns the headers of the table.
+    ///
+    /// This will be empty if the table had no `<th>` tags in its first row. See
+    /// [`Headers`](type.Headers.html) for more. Any `<td>` cells in a header
+    /// row, such as an empty cell above a column of row headers, also count as
+    /// headers so that the columns line up.
+    ///
+    /// If the table has a `<thead>` with several rows, the headers are taken
+    /// from the last one. Both `<th>` and `<td>` cells in the `<thead>` count
+    /// as headers, and cells from earlier rows with a `rowspan` reaching the
+    /// last row are included.
+    pub fn headers(&self) -> &Headers {
+        &self.headers.map
+    }
+
+    /// Returns true if the table has a header row.
+    ///
+    /// This distinguishes a table whose header row is blank, which has headers
+    /// that are empty strings, from a table without a header row, in which
+    /// case [`headers`](#method.headers) is empty. Headers named after `<col>`
+    /// elements with
+    /// [`TableBuilder::col_names`](struct.TableBuilder.html#method.col_names)
+    /// do not count, since they are not a row of the table.
+    ///
+    /// ```
+    /// # use table_extract::Table;
+    /// let html = "<table><tr><th>Name</th></tr><tr><td>John</td></tr></table>";
+    /// assert!(Table::find_first(html).unwrap().has_header_row());
+    /// let html = "<table><tr><td>Name</td></tr><tr><td>John</td></tr></table>";
+    /// assert!(!Table::find_first(html).unwrap().has_header_row());
+    /// ```
+    pub fn has_header_row(&self) -> bool {
+        self.headers.has_row
+    }
+
+    /// Returns the contents of the table's `<caption>`, if it has one.
+    ///
+    /// The caption is extracted in the same way as cell contents.
+    pub fn caption(&self) -> Option<&str> {
+        self.caption.as_deref()
+    }
+
+    /// Returns the `id` attribute of the `<table>` element, if it has one.
+    ///
+    /// This helps to tell which table was found by functions like
+    /// [`find_first`](#method.find_first) and
+    /// [`find_by_headers`](#method.find_by_headers).
+    pub fn id(&self) -> Option<&str> {
+        self.id.as_deref()
+    }
+
+    /// Returns the classes of the `<table>` element, in the order they appear
+    /// in its `class` attribute.
+    pub fn classes(&self) -> Vec<&str> {
+        self.classes.iter().map(String::as_str).collect()
+    }
+
+    /// Returns the header names ordered by their column positions.
+    ///
+    /// This will be empty if the table has no headers. Unlike
+    /// [`headers`](#method.headers), it includes repeated header names. See
+    /// [`header_slice`](#method.header_slice) to borrow them without
+    /// allocating.
+    pub fn header_names(&self) -> Vec<&str> {
+        self.header_slice().iter().map(String::as_str).collect()
+    }
+
+    /// Returns the header names ordered by their column positions, like
+    /// [`header_names`](#method.header_names), as a slice.
+    ///
+    /// The names are stored in this order when the table is built, so this
+    /// neither allocates nor sorts, and the order is the same on every call.
+    ///
+    /// ```
+    /// # use table_extract::Table;
+    /// let html = "<table><tr><th>Name</th><th>Age</th></tr></table>";
+    /// let table = Table::find_first(html).unwrap();
+    /// assert_eq!(["Name", "Age"], table.header_slice());
+    /// ```
+    pub fn header_slice(&self) -> &[String] {
+        &self.headers.names
+    }
+
+    /// Returns each header name paired with its zero-based column position,
+    /// sorted by position.
+    ///
+    /// Like [`header_names`](#method.header_names), this includes repeated
+    /// header names. The positions are not necessarily contiguous: a header
+    /// with a `colspan` is paired with its first column only.
+    pub fn headers_sorted(&self) -> Vec<(&str, usize)> {
+        let list = self.headers.list();
+        let mut headers: Vec<_> =
+            list.map(|(name, i)| (name.as_str(), i)).collect();
+        headers.sort_by_key(|&(_, i)| i);
+        headers
+    }
+
+    /// Returns an iterator over the [`Row`](struct.Row.html)s of the table.
+    ///
+    /// Both `<th>` and `<td>` cells are included in rows; see
+    /// [`Row::row_header`](struct.Row.html#method.row_header) for rows that
+    /// begin with a `<th>`. If the first row of the table is a header row,
+    /// meaning it contains at least one `<th>` cell, the iterator will start on
+    /// the second row. Use
+    /// [`headers`](#method.headers) to access the header row in that case.
+    ///
+    /// If the table has a `<thead>`, all of its rows are header rows instead,
+    /// and the iterator covers all the rows outside it. Rows in a `<tfoot>` are
+    /// never included; use [`footer`](#method.footer) to access them. Neither
+    /// are the rows of tables nested inside the table's cells.
+    /// Rows written without a `<tbody>` are placed in one by the HTML parser,
+    /// and are treated the same as rows in an explicit `<tbody>`.
+    ///
+    /// The rows borrow from the table. To consume the table and take ownership
+    /// of its rows instead, use its implementation of `IntoIterator`, which
+    /// yields [`OwnedRow`](struct.OwnedRow.html)s.
+    ///
+    /// A cell with a `rowspan` or `colspan` attribute is repeated in each of the
+    /// rows and columns it covers, so that the other cells stay in the correct
+    /// columns. Likewise, a header with a `colspan` refers to the first column
+    /// it covers.
+    pub fn iter(&self) -> Iter<'_> {
+        Iter {
+            headers: &self.headers,
+            iter: self.data.iter(),
+            cells: self.cells.iter(),
+            rows: self.rows.iter(),
+        }
+    }
+
+    /// Like [`iter`](#method.iter), but pairs each row with its zero-based
+    /// position among all the `<tr>` elements of the table.
+    ///
+    /// The positions count header and footer rows too, in document order, so
+    /// they can be used to find a row in the original HTML. For example, the
+    /// first row has position 1 if the table begins with a header row:
+    ///
+    /// ```
+    /// # use table_extract::Table;
+    /// let html = r#"
+    /// <table>
+    ///     <tr><th>Name</th></tr>
+    ///     <tr><td>John</td></tr>
+    /// </table>
+    /// "#;
+    /// let table = Table::find_first(html).unwrap();
+    /// let (index, row) = table.iter_with_source_row().next().unwrap();
+    /// assert_eq!((1, Some("John")), (index, row.get("Name")));
+    /// ```
+    pub fn iter_with_source_row(
+        &self,
+    ) -> impl Iterator<Item = (usize, Row<'_>)> + '_ {
+        self.rows.iter().map(|tr| tr.source).zip(self.iter())
+    }
+
+    /// Returns an iterator over the rows covered by [`iter`](#method.iter) for
+    /// which `f` returns `true`.
+    ///
+    /// ```
+    /// # use table_extract::Table;
+    /// let html = r#"
+    /// <table>
+    ///     <tr><th>Name</th><th>Status</th></tr>
+    ///     <tr><td>John</td><td>active</td></tr>
+    ///     <tr><td>May</td><td>inactive</td></tr>
+    /// </table>
+    /// "#;
+    /// let table = Table::find_first(html).unwrap();
+    /// let names: Vec<_> = table
+    ///     .rows_where(|r| r.get("Status") == Some("active"))
+    ///     .map(|r| r.get("Name").unwrap())
+    ///     .collect();
+    /// assert_eq!(vec!["John"], names);
+    /// ```
+    pub fn rows_where<'a, F>(
+        &'a self,
+        f: F,
+    ) -> impl Iterator<Item = Row<'a>> + 'a
+    where
+        F: Fn(&Row<'a>) -> bool + 'a,
+    {
+        self.iter().filter(move |row| f(row))
+    }
+
+    /// Returns an iterator over the rows covered by [`iter`](#method.iter) in
+    /// groups of `n` consecutive rows.
+    ///
+    /// This suits tables that spread each record over several rows. If the
+    /// number of rows is not a multiple of `n`, the last group has the rows
+    /// that are left over.
+    ///
+    /// Panics if `n` is 0.
+    ///
+    /// ```
+    /// # use table_extract::Table;
+    /// let html = r#"
+    ///     <table>
+    ///         <tr><th>Name</th></tr>
+    ///         <tr><td>John</td></tr>
+    ///         <tr><td>20</td></tr>
+    ///         <tr><td>May</td></tr>
+    ///         <tr><td>30</td></tr>
+    ///     </table>
+    /// "#;
+    /// let table = Table::find_first(html).unwrap();
+    /// let records: Vec<_> = table
+    ///     .chunks(2)
+    ///     .map(|rows| (rows[0].get_index(0), rows[1].get_index(0)))
+    ///     .collect();
+    /// assert_eq!(
+    ///     vec![(Some("John"), Some("20")), (Some("May"), Some("30"))],
+    ///     records
+    /// );
+    /// ```
+    pub fn chunks(&self, n: usize) -> impl Iterator<Item = Vec<Row<'_>>> {
+        assert!(n != 0, "chunk size must be non-zero");
+        let mut rows = self.iter();
+        std::iter::from_fn(move || {
+            let chunk: Vec<_> = rows.by_ref().take(n).collect();
+            Some(chunk).filter(|chunk| !chunk.is_empty())
+        })
+    }
+
+    /// Returns the rows covered by [`iter`](#method.iter), sorted by the
+    /// contents of their cells underneath `header`.
+    ///
+    /// Cells are compared as strings. The sort is stable, so rows with equal
+    /// cells keep their order. Rows too short to have a cell underneath
+    /// `header` come last, so if there is no such header, the rows are
+    /// returned in their original order. The table itself is not changed. See
+    /// [`sorted_by_numeric`](#method.sorted_by_numeric) to compare numbers by
+    /// value.
+    ///
+    /// ```
+    /// # use table_extract::Table;
+    /// let html = r#"
+    /// <table>
+    ///     <tr><th>Name</th><th>Age</th></tr>
+    ///     <tr><td>May</td><td>30</td></tr>
+    ///     <tr><td>John</td><td>9</td></tr>
+    /// </table>
+    /// "#;
+    /// let table = Table::find_first(html).unwrap();
+    /// let names: Vec<_> = table
+    ///     .sorted_by("Name")
+    ///     .iter()
+    ///     .map(|row| row.get("Name").unwrap())
+    ///     .collect();
+    /// assert_eq!(vec!["John", "May"], names);
+    /// ```
+    pub fn sorted_by(&self, header: &str) -> Vec<Row<'_>> {
+        self.sorted_with(header, |a, b| a.cmp(b))
+    }
+
+    /// Like [`sorted_by`](#method.sorted_by), but compares cells that parse as
+    /// numbers by their values.
+    ///
+    /// Cells are trimmed and parsed as `f64`. Cells that are numbers come
+    /// first, in ascending order, followed by the others in string order, and
+    /// then the rows without a cell underneath `header`.
+    ///
+    /// ```
+    /// # use table_extract::Table;
+    /// let html = r#"
+    /// <table>
+    ///     <tr><th>Name</th><th>Age</th></tr>
+    ///     <tr><td>May</td><td>30</td></tr>
+    ///     <tr><td>John</td><td>9</td></tr>
+    ///     <tr><td>Bob</td><td>unknown</td></tr>
+    /// </table>
+    /// "#;
+    /// let table = Table::find_first(html).unwrap();
+    /// let ages: Vec<_> = table
+    ///     .sorted_by_numeric("Age")
+    ///     .iter()
+    ///     .map(|row| row.get("Age").unwrap())
+    ///     .collect();
+    /// assert_eq!(vec!["9", "30", "unknown"], ages);
+    /// ```
+    pub fn sorted_by_numeric(&self, header: &str) -> Vec<Row<'_>> {
+        self.sorted_with(header, |a, b| {
+            match (a.trim().parse::<f64>(), b.trim().parse::<f64>()) {
+                (Ok(a), Ok(b)) => a.total_cmp(&b),
+                (Ok(_), Err(_)) => Ordering::Less,
+                (Err(_), Ok(_)) => Ordering::Greater,
+                (Err(_), Err(_)) => a.cmp(b),
+            }
+        })
+    }
+
+    /// Returns the rows sorted by their cells underneath `header` using
+    /// `compare`, with rows that have no such cell last.
+    fn sorted_with<F>(&self, header: &str, compare: F) -> Vec<Row<'_>>
+    where
+        F: Fn(&str, &str) -> Ordering,
+    {
+        let mut rows: Vec<_> = self.iter().collect();
+        rows.sort_by(|a, b| match (a.get(header), b.get(header)) {
+            (Some(a), Some(b)) => compare(a, b),
+            (Some(_), None) => Ordering::Less,
+            (None, Some(_)) => Ordering::Greater,
+            (None, None) => Ordering::Equal,
+        });
+        rows
+    }
+
+    /// Returns the header row as a [`Row`](struct.Row.html), so that it can be
+    /// handled in the same way as the rows returned by [`iter`](#method.iter).
+    ///
+    /// The row has a cell for each column spanned by the headers, in order. A
+    /// header spanning several columns is repeated in each of them, and
+    /// columns without a header are empty. Looking up a header in the row
+    /// returns the header itself. Returns `None` if the table has no headers.
+    ///
+    /// ```
+    /// # use table_extract::Table;
+    /// let html = r#"
+    ///     <table>
+    ///         <tr><th>Name</th><th>Age</th></tr>
+    ///         <tr><td>John</td><td>20</td></tr>
+    ///     </table>
+    /// "#;
+    /// let table = Table::find_first(html).unwrap();
+    /// let header = table.header_row().unwrap();
+    /// assert_eq!(&["Name", "Age"], header.as_slice());
+    /// assert_eq!(Some("Age"), header.get("Age"));
+    /// ```
+    pub fn header_row(&self) -> Option<Row<'_>> {
+        if self.headers.names.is_empty() {
+            return None;
+        }
+        Some(Row {
+            headers: &self.headers,
+            cells: &self.headers.row,
+            info: &self.headers.cells,
+            tr: &NO_ROW,
+        })
+    }
+
+    /// Returns the number of rows in the table, counting in the same way as
+    /// [`iter`](#method.iter).
+    pub fn len(&self) -> usize {
+        self.data.len()
+    }
+
+    /// Returns `true` if the table has no rows, counting in the same way as
+    /// [`iter`](#method.iter).
+    ///
+    /// A table with headers but no other rows is empty.
+    pub fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+
+    /// Returns the number of columns in the table, which is the length of the
+    /// longest of the header row and the rows covered by [`iter`](#method.iter).
+    ///
+    /// Columns are counted after expanding `colspan` attributes, so a row with
+    /// a single `<td colspan="4">` spans 4 columns. Rows can still be shorter
+    /// than this if the source has fewer cells in them; see
+    /// [`ragged_rows`](#method.ragged_rows). If the table was parsed with
+    /// [`TableBuilder::expand_spans`] disabled, each cell counts as a single
+    /// column instead.
+    ///
+    /// ```
+    /// # use table_extract::Table;
+    /// let html = r#"
+    ///     <table>
+    ///         <tr><th>A</th><th>B</th><th>C</th></tr>
+    ///         <tr><td colspan="2">1</td><td>2</td></tr>
+    ///     </table>
+    /// "#;
+    /// let table = Table::find_first(html).unwrap();
+    /// assert_eq!(3, table.num_columns());
+    /// ```
+    ///
+    /// [`TableBuilder::expand_spans`]: struct.TableBuilder.html#method.expand_spans
+    pub fn num_columns(&self) -> usize {
+        let rows = self.data.iter().map(Vec::len);
+        rows.fold(self.headers.row.len(), usize::max)
+    }
+
+    /// Returns the number of headers, which is the size of the map returned by
+    /// [`headers`](#method.headers).
+    ///
+    /// A name shared by several columns is counted once. If this is less than
+    /// [`num_columns`](#method.num_columns), some columns can only be reached
+    /// by position, which often means the header row does not describe all of
+    /// the data:
+    ///
+    /// ```
+    /// # use table_extract::Table;
+    /// let html = r#"
+    ///     <table>
+    ///         <tr><th>Name</th><th>Age</th></tr>
+    ///         <tr><td>John</td><td>20</td><td>extra</td></tr>
+    ///     </table>
+    /// "#;
+    /// let table = Table::find_first(html).unwrap();
+    /// assert_eq!(2, table.num_header_columns());
+    /// assert_eq!(3, table.num_columns());
+    /// ```
+    pub fn num_header_columns(&self) -> usize {
+        self.headers.map.len()
+    }
+
+    /// Returns the contents of the table's cells, one `Vec` per row.
+    ///
+    /// This covers the same rows as [`iter`](#method.iter), so the header row
+    /// and footer are not included. Rows may have different lengths.
+    pub fn data(&self) -> &[Vec<String>] {
+        &self.data
+    }
+
+    /// Consumes the table and returns the contents of its cells, one `Vec` per
+    /// row, as in [`data`](#method.data).
+    ///
+    /// This is convenient for handing the grid to a CSV writer or a dataframe
+    /// library.
+    pub fn into_data(self) -> Vec<Vec<String>> {
+        self.data
+    }
+
+    /// Returns a copy of the contents of the table's cells, with each row
+    /// padded with empty strings to [`num_columns`](#method.num_columns).
+    ///
+    /// Like [`data`](#method.data), this covers the rows covered by
+    /// [`iter`](#method.iter), so the header row is not included. Every row
+    /// has the same length, so the result can be flattened into a matrix of
+    /// `len() * num_columns()` strings in row-major order. Padding hides
+    /// missing cells, so check [`is_rectangular`](#method.is_rectangular)
+    /// first if their absence matters.
+    ///
+    /// ```
+    /// # use table_extract::Table;
+    /// let html = r#"
+    ///     <table>
+    ///         <tr><th>Name</th><th>Age</th></tr>
+    ///         <tr><td>John</td><td>20</td></tr>
+    ///         <tr><td>May</td></tr>
+    ///     </table>
+    /// "#;
+    /// let table = Table::find_first(html).unwrap();
+    /// assert_eq!(
+    ///     vec![vec!["John", "20"], vec!["May", ""]],
+    ///     table.to_padded_data()
+    /// );
+    /// ```
+    pub fn to_padded_data(&self) -> Vec<Vec<String>> {
+        let width = self.num_columns();
+        self.data
+            .iter()
+            .map(|row| {
+                let mut row = row.clone();
+                row.resize(width, String::new());
+                row
+            })
+            .collect()
+    }
+
+    /// Returns a copy of the contents of the table's cells as an
+    /// [ndarray](https://docs.rs/ndarray) matrix. Requires the `ndarray`
+    /// feature.
+    ///
+    /// The matrix has a row for each row covered by [`iter`](#method.iter),
+    /// so the header row is not included, and
+    /// [`num_columns`](#method.num_columns) columns. As in
+    /// [`to_padded_data`](#method.to_padded_data), rows too short to reach a
+    /// column are padded with empty strings.
+    ///
+    /// ```
+    /// # use table_extract::Table;
+    /// let html = r#"
+    ///     <table>
+    ///         <tr><th>Name</th><th>Age</th></tr>
+    ///         <tr><td>John</td><td>20</td></tr>
+    ///         <tr><td>May</td></tr>
+    ///     </table>
+    /// "#;
+    /// let table = Table::find_first(html).unwrap();
+    /// let array = table.to_array2();
+    /// assert_eq!(&[2, 2], array.shape());
+    /// assert_eq!("20", array[[0, 1]]);
+    /// assert_eq!("", array[[1, 1]]);
+    /// ```
+    #[cfg(feature = "ndarray")]
+    pub fn to_array2(&self) -> Array2<String> {
+        Array2::from_shape_fn((self.len(), self.num_columns()), |(i, j)| {
+            self.cell(i, j).unwrap_or_default().to_string()
+        })
+    }
+
+    /// Like [`to_array2`](#method.to_array2), but parses every cell as an
+    /// `f64`. Requires the `ndarray` feature.
+    ///
+    /// Missing cells are not padded. Returns a
+    /// [`RowError`](enum.RowError.html) naming the header of the first cell in
+    /// row-major order that is missing or not a number.
+    ///
+    /// ```
+    /// # use table_extract::{RowError, Table};
+    /// let html = r#"
+    ///     <table>
+    ///         <tr><th>X</th><th>Y</th></tr>
+    ///         <tr><td>1</td><td>2.5</td></tr>
+    ///         <tr><td>3</td><td>n/a</td></tr>
+    ///     </table>
+    /// "#;
+    /// let table = Table::find_first(html).unwrap();
+    /// match table.to_array2_f64() {
+    ///     Err(RowError::InvalidValue { header, value, .. }) => {
+    ///         assert_eq!(("Y", "n/a"), (header.as_str(), value.as_str()));
+    ///     }
+    ///     other => panic!("unexpected result: {:?}", other),
+    /// }
+    /// ```
+    #[cfg(feature = "ndarray")]
+    pub fn to_array2_f64(&self) -> Result<Array2<f64>, RowError> {
+        let mut array = Array2::zeros((self.len(), self.num_columns()));
+        for ((i, j), value) in array.indexed_iter_mut() {
+            let header = self.headers.row.get(j).map_or("", String::as_str);
+            let cell = self
+                .cell(i, j)
+                .ok_or_else(|| RowError::MissingColumn(header.to_string()))?;
+            *value = cell.parse().map_err(|e: ParseFloatError| {
+                RowError::InvalidValue {
+                    header: header.to_string(),
+                    value: cell.to_string(),
+                    message: e.to_string(),
+                }
+            })?;
+        }
+        Ok(array)
+    }
+
+    /// Returns the [`Row`](struct.Row.html) at the zero-based position
+    /// `index`, counting in the same way as [`iter`](#method.iter).
+    ///
+    /// Returns `None` if the table does not have that many rows.
+    pub fn row(&self, index: usize) -> Option<Row<'_>> {
+        self.data.get(index).map(|cells| Row {
+            headers: &self.headers,
+            cells,
+            info: self.cells.get(index).map_or(&[], Vec::as_slice),
+            tr: self.rows.get(index).unwrap_or(&NO_ROW),
+        })
+    }
+
+    /// Returns the contents of the cell in the zero-based row `row` and column
+    /// `col`, counting rows in the same way as [`iter`](#method.iter), so the
+    /// header row is not included.
+    ///
+    /// Returns `None` if the table does not have that many rows, or if the row
+    /// is too short to have that many cells.
+    ///
+    /// ```
+    /// # use table_extract::Table;
+    /// let html = r#"
+    /// <table>
+    ///     <tr><th>Name</th><th>Age</th></tr>
+    ///     <tr><td>John</td><td>20</td></tr>
+    /// </table>
+    /// "#;
+    /// let table = Table::find_first(html).unwrap();
+    /// assert_eq!(Some("20"), table.cell(0, 1));
+    /// assert_eq!(None, table.cell(1, 0));
+    /// ```
+    pub fn cell(&self, row: usize, col: usize) -> Option<&str> {
+        self.data.get(row)?.get(col).map(String::as_str)
+    }
+
+    /// Returns the cells underneath `header` in each row, skipping rows that
+    /// are too short to have one.
+    ///
+    /// Returns `None` if there is no such header.
+    pub fn column(&self, header: &str) -> Option<Vec<&str>> {
+        self.headers.index(header).map(|i| self.column_index(i))
+    }
+
+    /// Returns the cells at the zero-based position `index` in each row,
+    /// skipping rows that are too short to have one.
+    pub fn column_index(&self, index: usize) -> Vec<&str> {
+        self.iter().filter_map(|row| row.get_index(index)).collect()
+    }
+
+    /// Returns the table's columns, each with the name of its header and its
+    /// cells from the rows covered by [`iter`](#method.iter).
+    ///
+    /// There are [`num_columns`](#method.num_columns) columns. Rows too short
+    /// to reach a column have `None` in it, and columns without a header are
+    /// named with an empty string. This column-oriented form is what data
+    /// frame libraries expect, with `None` becoming a null value; see
+    /// [`into_dataframe`](#method.into_dataframe) for polars.
+    ///
+    /// ```
+    /// # use table_extract::Table;
+    /// let html = r#"
+    ///     <table>
+    ///         <tr><th>Name</th><th>Age</th></tr>
+    ///         <tr><td>John</td><td>20</td></tr>
+    ///         <tr><td>May</td></tr>
+    ///     </table>
+    /// "#;
+    /// let table = Table::find_first(html).unwrap();
+    /// let columns = table.to_columns();
+    /// assert_eq!(("Name", vec![Some("John"), Some("May")]), columns[0]);
+    /// assert_eq!(("Age", vec![Some("20"), None]), columns[1]);
+    /// ```
+    pub fn to_columns(&self) -> Vec<(&str, Vec<Option<&str>>)> {
+        (0..self.num_columns())
+            .map(|i| {
+                let name = self.headers.row.get(i).map_or("", String::as_str);
+                let cells = self.iter().map(|row| row.get_index(i)).collect();
+                (name, cells)
+            })
+            .collect()
+    }
+
+    /// Converts the table into a [polars](https://docs.rs/polars) `DataFrame`.
+    /// Requires the `polars` feature.
+    ///
+    /// The data frame has the columns of [`to_columns`](#method.to_columns),
+    /// each a column of strings named after its header, and one row for each
+    /// row covered by [`iter`](#method.iter). Rows too short to reach a column
+    /// are padded with nulls. Returns an error if two columns have the same
+    /// name, as happens when a table has several columns without a header.
+    ///
+    /// ```
+    /// # use table_extract::Table;
+    /// let html = r#"
+    ///     <table>
+    ///         <tr><th>Name</th><th>Age</th></tr>
+    ///         <tr><td>John</td><td>20</td></tr>
+    ///         <tr><td>May</td></tr>
+    ///     </table>
+    /// "#;
+    /// let table = Table::find_first(html).unwrap();
+    /// let df = table.into_dataframe().unwrap();
+    /// assert_eq!((2, 2), df.shape());
+    /// assert_eq!(1, df.column("Age").unwrap().null_count());
+    /// ```
+    #[cfg(feature = "polars")]
+    pub fn into_dataframe(&self) -> PolarsResult<DataFrame> {
+        use polars::prelude::{Column, NamedFrom, Series};
+
+        let columns = self
+            .to_columns()
+            .into_iter()
+            .map(|(name, cells)| Column::from(Series::new(name.into(), cells)))
+            .collect();
+        DataFrame::new(self.len(), columns)
+    }
+
+    /// Returns the number of rows with each distinct value underneath
+    /// `header`, skipping rows that are too short to have one.
+    ///
+    /// Returns `None` if there is no such header.
+    ///
+    /// ```
+    /// # use table_extract::Table;
+    /// let html = r#"
+    ///     <table>
+    ///         <tr><th>Name</th><th>Status</th></tr>
+    ///         <tr><td>John</td><td>active</td></tr>
+    ///         <tr><td>May</td><td>inactive</td></tr>
+    ///         <tr><td>Bob</td><td>active</td></tr>
+    ///     </table>
+    /// "#;
+    /// let table = Table::find_first(html).unwrap();
+    /// let counts = table.value_counts("Status").unwrap();
+    /// assert_eq!(Some(&2), counts.get("active"));
+    /// assert_eq!(Some(&1), counts.get("inactive"));
+    /// ```
+    pub fn value_counts(&self, header: &str) -> Option<HashMap<&str, usize>> {
+        let mut counts = HashMap::new();
+        for value in self.column(header)? {
+            *counts.entry(value).or_insert(0) += 1;
+        }
+        Some(counts)
+    }
+
+    /// Returns an iterator over the [`Row`](struct.Row.html)s in the table's
+    /// `<tfoot>`, such as a row of totals.
+    ///
+    /// This is empty if the table has no `<tfoot>`. The rows share the headers
+    /// of the table, and are otherwise treated the same as in
+    /// [`iter`](#method.iter).
+    pub fn footer(&self) -> Iter<'_> {
+        Iter {
+            headers: &self.headers,
+            iter: self.footer.iter(),
+            cells: self.cells.get(self.data.len()..).unwrap_or_default().iter(),
+            rows: self.rows.get(self.data.len()..).unwrap_or_default().iter(),
+        }
+    }
+
+    /// Converts each row of the table into a `T`, covering the same rows as
+    /// [`iter`](#method.iter).
+    ///
+    /// Rows that fail to convert produce an error without stopping the
+    /// iteration. See [`FromRow`](trait.FromRow.html) for an example.
+    pub fn deserialize<T>(
+        &self,
+    ) -> impl Iterator<Item = Result<T, RowError>> + '_
+    where
+        T: FromRow,
+    {
+        self.iter().map(|row| T::from_row(&row))
+    }
+
+    /// Guesses the type of each column from the contents of its cells.
+    ///
+    /// A column is classified by the first of `Integer`, `Float`, and
+    /// `Boolean` that every non-empty cell in it parses as, ignoring
+    /// surrounding whitespace, or as `Text` otherwise. The result has an entry
+    /// for every column that has a header or a cell in some row, in order.
+    ///
+    /// ```
+    /// use table_extract::{ColumnType, Table};
+    ///
+    /// let html = r#"
+    ///     <table>
+    ///         <tr><th>Name</th><th>Age</th><th>Height</th></tr>
+    ///         <tr><td>John</td><td>20</td><td>1.8</td></tr>
+    ///         <tr><td>May</td><td></td><td>2</td></tr>
+    ///     </table>
+    /// "#;
+    /// let table = Table::find_first(html).unwrap();
+    /// assert_eq!(
+    ///     vec![ColumnType::Text, ColumnType::Integer, ColumnType::Float],
+    ///     table.infer_schema()
+    /// );
+    /// ```
+    pub fn infer_schema(&self) -> Vec<ColumnType> {
+        (0..self.num_columns())
+            .map(|i| {
+                let cells: Vec<_> = self
+                    .column_index(i)
+                    .into_iter()
+                    .map(str::trim)
+                    .filter(|cell| !cell.is_empty())
+                    .collect();
+                let all = |parses: fn(&str) -> bool| {
+                    !cells.is_empty() && cells.iter().all(|cell| parses(cell))
+                };
+                if all(|cell| cell.parse::<i64>().is_ok()) {
+                    ColumnType::Integer
+                } else if all(|cell| cell.parse::<f64>().is_ok()) {
+                    ColumnType::Float
+                } else if all(|cell| cell.parse::<bool>().is_ok()) {
+                    ColumnType::Boolean
+                } else {
+                    ColumnType::Text
+                }
+            })
+            .collect()
+    }
+
+    /// Returns true if every row covered by [`iter`](#method.iter) has the same
+    /// number of cells, which is also the number of columns spanned by the
+    /// headers if there are any.
+    ///
+    /// See [`ragged_rows`](#method.ragged_rows) to find the rows that do not.
+    pub fn is_rectangular(&self) -> bool {
+        self.ragged_rows().is_empty()
+    }
+
+    /// Returns the zero-based indices of the rows covered by
+    /// [`iter`](#method.iter) whose length differs from the expected number of
+    /// columns.
+    ///
+    /// If the table has headers, the expected number is the number of columns
+    /// they span. Otherwise, it is the most common row length, preferring the
+    /// longer one in a tie. Short rows return `None` from
+    /// [`Row::get`](struct.Row.html#method.get) for their missing cells.
+    ///
+    /// Lengths are counted after expanding `colspan` and `rowspan` attributes,
+    /// so a row whose cells span all the columns is not ragged even if it has
+    /// fewer cells in the source.
+    ///
+    /// ```
+    /// # use table_extract::Table;
+    /// let html = r#"
+    ///     <table>
+    ///         <tr><th>Name</th><th>Age</th></tr>
+    ///         <tr><td>John</td><td>20</td></tr>
+    ///         <tr><td>May</td></tr>
+    ///         <tr><td>Bob</td><td>30</td><td>extra</td></tr>
+    ///     </table>
+    /// "#;
+    /// let table = Table::find_first(html).unwrap();
+    /// assert_eq!(vec![1, 2], table.ragged_rows());
+    /// assert!(!table.is_rectangular());
+    /// ```
+    pub fn ragged_rows(&self) -> Vec<usize> {
+        let columns = if self.headers.names.is_empty() {
+            let mut counts = HashMap::new();
+            for row in &self.data {
+                *counts.entry(row.len()).or_insert(0) += 1;
+            }
+            let modal = counts.into_iter().max_by_key(|&(len, n)| (n, len));
+            modal.map_or(0, |(len, _)| len)
+        } else {
+            self.headers.row.len()
+        };
+        let rows = self.data.iter().enumerate();
+        rows.filter(|(_, row)| row.len() != columns)
+            .map(|(i, _)| i)
+            .collect()
+    }
+
+    /// Returns the width of each column, which is the length of its longest
+    /// line across the headers and the rows covered by [`iter`](#method.iter).
+    ///
+    /// Lengths are counted in `char`s, not graphemes, so a letter followed by
+    /// a combining accent counts as two. Browsers and HTML parsers generally
+    /// produce precomposed text, so accented text in practice still has one
+    /// `char` per letter. Wide characters such as CJK ideographs also count as
+    /// one. A header spanning several columns only counts towards its first
+    /// one. The result has an entry for every column that has a header or a
+    /// cell.
+    ///
+    /// ```
+    /// # use table_extract::Table;
+    /// let html = r#"
+    ///     <table>
+    ///         <tr><th>Name</th><th>Age</th></tr>
+    ///         <tr><td>José</td><td>20</td></tr>
+    ///         <tr><td>Maria</td><td>7</td></tr>
+    ///     </table>
+    /// "#;
+    /// let table = Table::find_first(html).unwrap();
+    /// assert_eq!(vec![5, 3], table.column_widths());
+    /// ```
+    pub fn column_widths(&self) -> Vec<usize> {
+        let headers = self.headers.list().map(|(name, i)| (i, name));
+        let cells = self.data.iter().flat_map(|row| row.iter().enumerate());
+        let mut widths = Vec::new();
+        for (i, content) in headers.chain(cells) {
+            if widths.len() <= i {
+                widths.resize(i + 1, 0);
+            }
+            let lines = content.lines().map(|line| line.chars().count());
+            widths[i] = lines.fold(widths[i], usize::max);
+        }
+        widths
+    }
+
+    /// Converts the table back into an HTML string.
+    ///
+    /// The result is a `<table>` with a row of `<th>` cells for the headers,
+    /// if there are any, followed by a row of `<td>` cells for each row covered
+    /// by [`iter`](#method.iter). A header spanning several columns gets a
+    /// `colspan`. The caption and footer are not included.
+    ///
+    /// Cell contents are escaped, so they appear as text even if the table was
+    /// parsed with [`TextMode::Html`](enum.TextMode.html#variant.Html).
+    ///
+    /// ```
+    /// # use table_extract::Table;
+    /// let html = "<table><tr><th>A</th></tr><tr><td>1 &lt; 2</td></tr></table>";
+    /// let table = Table::find_first(html).unwrap();
+    /// assert_eq!(
+    ///     "<table><tr><th>A</th></tr><tr><td>1 &amp;lt; 2</td></tr></table>",
+    ///     table.to_html()
+    /// );
+    /// ```
+    pub fn to_html(&self) -> String {
+        let mut html = String::from("<table>");
+        let columns = &self.headers.columns;
+        if !columns.is_empty() {
+            html.push_str("<tr>");
+            for (i, (name, col)) in self.headers.list().enumerate() {
+                // The last header spans to the end of the header row.
+                let end = columns.get(i + 1).copied();
+                let end = end.unwrap_or(self.headers.row.len());
+                match end.saturating_sub(col) {
+                    span if span > 1 => {
+                        html.push_str(&format!("<th colspan=\"{}\">", span))
+                    }
+                    _ => html.push_str("<th>"),
+                }
+                html.push_str(&escape_html(name));
+                html.push_str("</th>");
+            }
+            html.push_str("</tr>");
+        }
+        for row in &self.data {
+            html.push_str("<tr>");
+            for cell in row {
+                html.push_str("<td>");
+                html.push_str(&escape_html(cell));
+                html.push_str("</td>");
+            }
+            html.push_str("</tr>");
+        }
+        html.push_str("</table>");
+        html
+    }
+
+    /// Renders the table as a bordered grid of text, for debugging and
+    /// command-line output.
+    ///
+    /// The headers, if there are any, come first and are followed by a
+    /// separator line. Each row covered by [`iter`](#method.iter) follows, with
+    /// missing cells shown as empty. Cells are padded to the
+    /// [`column_widths`](#method.column_widths), and a cell with several lines
+    /// makes its row that many lines tall. The caption and footer are not
+    /// included. Returns an empty string if the table has no columns.
+    ///
+    /// ```
+    /// # use table_extract::Table;
+    /// let html = r#"
+    ///     <table>
+    ///         <tr><th>Name</th><th>Age</th></tr>
+    ///         <tr><td>John</td><td>20</td></tr>
+    ///         <tr><td>May</td></tr>
+    ///     </table>
+    /// "#;
+    /// let table = Table::find_first(html).unwrap();
+    /// assert_eq!(
+    ///     "\
+    /// +------+-----+
+    /// | Name | Age |
+    /// +------+-----+
+    /// | John | 20  |
+    /// | May  |     |
+    /// +------+-----+
+    /// ",
+    ///     table.to_ascii()
+    /// );
+    /// ```
+    pub fn to_ascii(&self) -> String {
+        let widths = self.column_widths();
+        if widths.is_empty() {
+            return String::new();
+        }
+        let mut border = String::from("+");
+        for width in &widths {
+            border.push_str(&"-".repeat(width + 2));
+            border.push('+');
+        }
+        border.push('\n');
+        let mut ascii = border.clone();
+        if !self.headers.names.is_empty() {
+            let mut names = vec![""; widths.len()];
+            for (name, i) in self.headers.list() {
+                names[i] = name;
+            }
+            push_ascii_row(&mut ascii, &names, &widths);
+            ascii.push_str(&border);
+        }
+        for row in &self.data {
+            let row: Vec<_> = row.iter().map(String::as_str).collect();
+            push_ascii_row(&mut ascii, &row, &widths);
+        }
+        if !self.data.is_empty() {
+            ascii.push_str(&border);
+        }
+        ascii
+    }
+
+    /// Combines several tables with the same headers into one, such as the
+    /// sections of a paginated report.
+    ///
+    /// The rows of each table are appended in order, and so are their footers.
+    /// The caption is taken from the first table. Positions reported by
+    /// [`iter_with_source_row`](#method.iter_with_source_row) still refer to
+    /// the table each row came from.
+    ///
+    /// Returns [`TableError::HeaderMismatch`] if any table's headers differ
+    /// from the first table's, in either their names or their columns, and
+    /// [`TableError::NoTableFound`] if `tables` is empty.
+    ///
+    /// [`TableError::HeaderMismatch`]: enum.TableError.html#variant.HeaderMismatch
+    /// [`TableError::NoTableFound`]: enum.TableError.html#variant.NoTableFound
+    pub fn concat(tables: &[Table]) -> Result<Table, TableError> {
+        let (first, rest) =
+            tables.split_first().ok_or(TableError::NoTableFound)?;
+        for (i, table) in rest.iter().enumerate() {
+            if table.headers.list().ne(first.headers.list()) {
+                return Err(TableError::HeaderMismatch {
+                    index: i + 1,
+                    expected: first.header_names_owned(),
+                    found: table.header_names_owned(),
+                });
+            }
+        }
+        let mut data = Vec::new();
+        let mut footer = Vec::new();
+        let mut cells = Vec::new();
+        let mut footer_cells = Vec::new();
+        let mut rows = Vec::new();
+        let mut footer_rows = Vec::new();
+        for table in tables {
+            let n = table.data.len();
+            data.extend_from_slice(&table.data);
+            footer.extend_from_slice(&table.footer);
+            let (body, foot) = table.cells.split_at(n.min(table.cells.len()));
+            cells.extend_from_slice(body);
+            footer_cells.extend_from_slice(foot);
+            let (body, foot) = table.rows.split_at(n.min(table.rows.len()));
+            rows.extend_from_slice(body);
+            footer_rows.extend_from_slice(foot);
+            // Keep the information aligned with the rows of later tables.
+            cells.resize(data.len(), Vec::new());
+            rows.resize(data.len(), RowInfo::default());
+            footer_cells.resize(footer.len(), Vec::new());
+            footer_rows.resize(footer.len(), RowInfo::default());
+        }
+        cells.extend(footer_cells);
+        rows.extend(footer_rows);
+        Ok(Table {
+            headers: first.headers.clone(),
+            data,
+            footer,
+            caption: first.caption.clone(),
+            cells,
+            rows,
+            id: first.id.clone(),
+            classes: first.classes.clone(),
+        })
+    }
+
+    fn header_names_owned(&self) -> Vec<String> {
+        self.header_names().into_iter().map(String::from).collect()
+    }
+
+    /// Swaps the rows and columns of the table, so that the cell in row `i`
+    /// and column `j` moves to row `j` and column `i`.
+    ///
+    /// If the table has headers, they are treated as an extra row before the
+    /// others, so they become the first column of the result, and the result
+    /// itself has no headers. Rows shorter than the longest one, and columns
+    /// without a header, are padded with empty cells first. The footer is left
+    /// out. [`iter_with_source_row`](#method.iter_with_source_row) reports the
+    /// column each row of the result came from.
+    ///
+    /// See [`transpose_with_headers`](#method.transpose_with_headers) for
+    /// tables laid out with records across the top.
+    pub fn transpose(&self) -> Table {
+        let mut grid = Vec::new();
+        let mut info = Vec::new();
+        if !self.headers.names.is_empty() {
+            let mut names = Vec::new();
+            for (name, i) in self.headers.list() {
+                names.resize(i, String::new());
+                names.push(name.clone());
+            }
+            info.push(vec![Cell::default(); names.len()]);
+            grid.push(names);
+        }
+        grid.extend(self.data.iter().cloned());
+        info.extend(self.cells.iter().take(self.data.len()).cloned());
+        info.resize(grid.len(), Vec::new());
+        let width = grid.iter().map(Vec::len).max().unwrap_or(0);
+        let mut data = vec![Vec::with_capacity(grid.len()); width];
+        let mut cells = vec![Vec::with_capacity(grid.len()); width];
+        for (row, row_info) in grid.into_iter().zip(info) {
+            let mut row = row.into_iter();
+            let mut row_info = row_info.into_iter();
+            for j in 0..width {
+                data[j].push(row.next().unwrap_or_default());
+                cells[j].push(row_info.next().unwrap_or_default());
+            }
+        }
+        Table {
+            headers: HeaderInfo::default(),
+            data,
+            footer: Vec::new(),
+            caption: self.caption.clone(),
+            cells,
+            rows: (0..width)
+                .map(|source| RowInfo {
+                    source,
+                    ..RowInfo::default()
+                })
+                .collect(),
+            id: self.id.clone(),
+            classes: self.classes.clone(),
+        }
+    }
+
+    /// Like [`transpose`](#method.transpose), but uses the first row of the
+    /// result, which was the first column of the table, as its headers.
+    ///
+    /// This suits tables with attribute names down the left column and a
+    /// record in each of the other columns:
+    ///
+    /// ```
+    /// # use table_extract::Table;
+    /// let html = r#"
+    /// <table>
+    ///     <tr><th>Name</th><td>John</td><td>May</td></tr>
+    ///     <tr><th>Age</th><td>20</td><td>30</td></tr>
+    /// </table>
+    /// "#;
+    /// let table = Table::find_first(html).unwrap().transpose_with_headers();
+    /// let ages: Vec<_> = table.iter().map(|row| row.get("Age")).collect();
+    /// assert_eq!(vec![Some("20"), Some("30")], ages);
+    /// ```
+    pub fn transpose_with_headers(&self) -> Table {
+        let mut table = self.transpose();
+        if table.data.is_empty() {
+            return table;
+        }
+        let names = table.data.remove(0);
+        table.headers.cells = table.cells.remove(0);
+        table.rows.remove(0);
+        for (i, name) in names.iter().enumerate() {
+            table.headers.map.entry(name.clone()).or_insert(i);
+            table.headers.push(name.clone(), i);
+        }
+        table.headers.row = names;
+        table.headers.has_row = true;
+        table
+    }
+
+    /// Removes the rows in which every cell is empty, including rows with no
+    /// cells at all, and returns the remaining table.
+    ///
+    /// Only the rows covered by [`iter`](#method.iter) are removed. The
+    /// headers and [`footer`](#method.footer) are left as they are.
+    pub fn without_empty_rows(mut self) -> Table {
+        // Pad missing information so that no row is dropped by the zip below.
+        let n = self.data.len();
+        self.cells.resize(self.cells.len().max(n), Vec::new());
+        self.rows.resize(self.rows.len().max(n), RowInfo::default());
+        let footer_cells = self.cells.split_off(n);
+        let footer_rows = self.rows.split_off(n);
+        let (data, (mut cells, mut rows)): (Vec<_>, (Vec<_>, Vec<_>)) = self
+            .data
+            .into_iter()
+            .zip(self.cells.into_iter().zip(self.rows))
+            .filter(|(row, _)| !row.iter().all(String::is_empty))
+            .unzip();
+        cells.extend(footer_cells);
+        rows.extend(footer_rows);
+        Table {
+            data,
+            cells,
+            rows,
+            ..self
+        }
+    }
+
+    /// Returns a copy of the table with only the columns at the zero-based
+    /// positions in `indices`, in that order.
+    ///
+    /// Positions past the last column are skipped. Each row keeps the cells at
+    /// the selected positions, with empty cells filling in for the ones it is
+    /// too short to have, except at its end, so short rows stay short. The
+    /// headers are moved to their new positions; a header spanning several
+    /// columns is kept only if its first column is selected. The footer is
+    /// narrowed in the same way, and the rest of the table is unchanged.
+    ///
+    /// ```
+    /// # use table_extract::Table;
+    /// let html = r#"
+    ///     <table>
+    ///         <tr><th>Id</th><th>Name</th><th>Notes</th><th>Age</th></tr>
+    ///         <tr><td>1</td><td>John</td><td>-</td><td>20</td></tr>
+    ///     </table>
+    /// "#;
+    /// let table = Table::find_first(html).unwrap().select_columns(&[3, 1, 9]);
+    /// assert_eq!(vec!["Age", "Name"], table.header_names());
+    /// assert_eq!(&[vec!["20", "John"]], table.data());
+    /// ```
+    pub fn select_columns(&self, indices: &[usize]) -> Table {
+        let columns = self.num_columns();
+        let kept: Vec<_> =
+            indices.iter().copied().filter(|&i| i < columns).collect();
+        let select = |row: &[String], info: &[Cell]| -> (Vec<_>, Vec<_>) {
+            let len = kept.iter().rposition(|&i| i < row.len());
+            let cells = kept[..len.map_or(0, |j| j + 1)].iter().map(|&i| {
+                let content = row.get(i).cloned().unwrap_or_default();
+                (content, info.get(i).cloned().unwrap_or_default())
+            });
+            cells.unzip()
+        };
+        let mut data = Vec::new();
+        let mut footer = Vec::new();
+        let mut cells = Vec::new();
+        let rows = self.data.iter().chain(&self.footer).enumerate();
+        for (i, row) in rows {
+            let info = self.cells.get(i).map_or(&[][..], Vec::as_slice);
+            let (row, info) = select(row, info);
+            if i < self.data.len() {
+                data.push(row);
+            } else {
+                footer.push(row);
+            }
+            cells.push(info);
+        }
+        let (row, info) = select(&self.headers.row, &self.headers.cells);
+        let mut headers = HeaderInfo {
+            row,
+            cells: info,
+            has_row: self.headers.has_row,
+            ..HeaderInfo::default()
+        };
+        let keys: Vec<_> =
+            self.headers.list().zip(&self.headers.keys).collect();
+        for (j, &i) in kept.iter().enumerate() {
+            if let Some(&((name, _), key)) =
+                keys.iter().find(|((_, col), _)| *col == i)
+            {
+                headers.names.push(name.clone());
+                headers.columns.push(j);
+                headers.keys.push(key.clone());
+            }
+        }
+        for (key, &i) in &self.headers.map {
+            if let Some(j) = kept.iter().position(|&col| col == i) {
+                headers.map.insert(key.clone(), j);
+            }
+        }
+        headers.ascii_fold = self.headers.ascii_fold;
+        // A repeated header whose first column was left out refers to the
+        // first of its columns that was selected instead.
+        for (key, &j) in headers.keys.iter().zip(&headers.columns) {
+            headers.map.entry(key.clone()).or_insert(j);
+        }
+        Table {
+            headers,
+            data,
+            footer,
+            caption: self.caption.clone(),
+            cells,
+            rows: self.rows.clone(),
+            id: self.id.clone(),
+            classes: self.classes.clone(),
+        }
+    }
+
+    /// Like [`select_columns`](#method.select_columns), but selects the
+    /// columns underneath `headers` instead of by position.
+    ///
+    /// Headers that the table does not have are skipped. If several columns
+    /// have the same header, the first one is selected.
+    pub fn select_columns_by_header<T>(&self, headers: &[T]) -> Table
+    where
+        T: AsRef<str>,
+    {
+        let indices: Vec<_> = headers
+            .iter()
+            .filter_map(|h| self.headers.index(h.as_ref()))
+            .collect();
+        self.select_columns(&indices)
+    }
+
+    /// Creates a table from a `<table>` element, extracting each cell's inner
+    /// HTML.
+    pub fn new(element: ElementRef) -> Table {
+        Table::new_with_mode(element, TextMode::Html)
+    }
+
+    /// Creates a table from a `<table>` element, extracting the contents of
+    /// each cell according to `mode`.
+    pub fn new_with_mode(element: ElementRef, mode: TextMode) -> Table {
+        TableBuilder::new().text_mode(mode).build(element)
+    }
+}
+
+/// Options for parsing tables.
+///
+/// Setters can be chained to configure how tables are parsed. Then, the
+/// `find_*` methods find a table in an HTML string like the corresponding
+/// functions on [`Table`](struct.Table.html), and [`build`](#method.build)
+/// creates a table from a `<table>` element:
+///
+/// ```
+/// use table_extract::TableBuilder;
+///
+/// let html = "<table><tr><td>John\n    <i>Smith</i></td></tr></table>";
+/// let table = TableBuilder::new()
+///     .collapse_whitespace(true)
+///     .text_only(true)
+///     .find_first(html)
+///     .unwrap();
+/// assert_eq!("John Smith", table.iter().next().unwrap().as_slice()[0]);
+/// ```
+///
+/// The default options are the ones used by the functions on `Table`.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TableBuilder {
+    mode: TextMode,
+    whitespace: Whitespace,
+    trim: bool,
+    expand_spans: bool,
+    span_fill: SpanFill,
+    col_names: bool,
+    line_breaks: bool,
+    first_row_as_data: bool,
+    header_row: Option<usize>,
+    skip_hidden_rows: bool,
+    pad_rows: bool,
+    row_data_attrs: bool,
+    cell_selector: Option<Selector>,
+    header_trailing: Option<String>,
+    combine_headers: bool,
+    header_separator: String,
+    header_search_rows: usize,
+    ascii_fold: bool,
+}
+
+impl Default for TableBuilder {
+    fn default() -> TableBuilder {
+        TableBuilder {
+            mode: TextMode::default(),
+            whitespace: Whitespace::default(),
+            trim: true,
+            expand_spans: true,
+            span_fill: SpanFill::default(),
+            col_names: false,
+            line_breaks: false,
+            first_row_as_data: false,
+            header_row: None,
+            skip_hidden_rows: false,
+            pad_rows: false,
+            row_data_attrs: false,
+            cell_selector: None,
+            header_trailing: None,
+            combine_headers: false,
+            header_separator: String::from(" / "),
+            header_search_rows: 1,
+            ascii_fold: false,
+        }
+    }
+}
+
+impl TableBuilder {
+    /// Creates a builder with the default options.
+    pub fn new() -> TableBuilder {
+        TableBuilder::default()
+    }
+
+    /// Sets how the contents of each cell are extracted.
+    pub fn text_mode(mut self, mode: TextMode) -> TableBuilder {
+        self.mode = mode;
+        self
+    }
+
+    /// Sets whether to use [`TextMode::Text`](enum.TextMode.html#variant.Text)
+    /// rather than [`TextMode::Html`](enum.TextMode.html#variant.Html).
+    pub fn text_only(self, yes: bool) -> TableBuilder {
+        self.text_mode(if yes { TextMode::Text } else { TextMode::Html })
+    }
+
+    /// Sets how whitespace within each cell is handled.
+    pub fn whitespace(mut self, whitespace: Whitespace) -> TableBuilder {
+        self.whitespace = whitespace;
+        self
+    }
+
+    /// Sets whether to use
+    /// [`Whitespace::Collapse`](enum.Whitespace.html#variant.Collapse) rather
+    /// than [`Whitespace::Preserve`](enum.Whitespace.html#variant.Preserve).
+    pub fn collapse_whitespace(self, yes: bool) -> TableBuilder {
+        self.whitespace(if yes {
+            Whitespace::Collapse
+        } else {
+            Whitespace::Preserve
+        })
+    }
+
+    /// Sets whether to remove leading and trailing whitespace from each cell.
+    /// This is enabled by default.
+    ///
+    /// Besides Unicode whitespace, which includes the non-breaking space
+    /// `U+00A0`, trimming removes invisible format characters such as the
+    /// zero-width space `U+200B` and the byte order mark `U+FEFF`. With
+    /// [`TextMode::Html`](enum.TextMode.html#variant.Html), it also removes
+    /// `&nbsp;` entities, which is how non-breaking spaces appear in HTML.
+    ///
+    /// When disabled, cells contain their exact inner HTML or text, which is
+    /// useful when leading spaces are meaningful. This applies to header cells
+    /// too, so looking up a cell requires the header's surrounding whitespace.
+    pub fn trim(mut self, yes: bool) -> TableBuilder {
+        self.trim = yes;
+        self
+    }
+
+    /// Sets whether to turn `<br>` tags and the boundaries of block-level
+    /// elements such as `<p>` and `<div>` into line breaks. This is disabled by
+    /// default, and only affects
+    /// [`TextMode::Text`](enum.TextMode.html#variant.Text).
+    ///
+    /// Each line is trimmed and has its whitespace collapsed separately, so
+    /// only the line breaks added for tags are kept when collapsing whitespace.
+    ///
+    /// ```
+    /// # use table_extract::TableBuilder;
+    /// let html = "<table><tr><td>Line1<br>Line2</td></tr></table>";
+    /// let table = TableBuilder::new()
+    ///     .text_only(true)
+    ///     .line_breaks(true)
+    ///     .find_first(html)
+    ///     .unwrap();
+    /// assert_eq!("Line1\nLine2", table.iter().next().unwrap().as_slice()[0]);
+    /// ```
+    pub fn line_breaks(mut self, yes: bool) -> TableBuilder {
+        self.line_breaks = yes;
+        self
+    }
+
+    /// Sets whether to honor `rowspan` and `colspan` attributes. This is
+    /// enabled by default.
+    ///
+    /// When disabled, each cell occupies a single position regardless of its
+    /// attributes, so the cells after it in the row may not line up with the
+    /// headers.
+    pub fn expand_spans(mut self, yes: bool) -> TableBuilder {
+        self.expand_spans = yes;
+        self
+    }
+
+    /// Sets what to put in the extra positions covered by a cell with a
+    /// `rowspan` or `colspan`. This has no effect on header rows, where the
+    /// contents are always duplicated.
+    pub fn span_fill(mut self, fill: SpanFill) -> TableBuilder {
+        self.span_fill = fill;
+        self
+    }
+
+    /// Sets whether to keep the first row as data even if it contains `<th>`
+    /// cells. This is disabled by default.
+    ///
+    /// When enabled, tables without a `<thead>` have no headers, and
+    /// [`Table::iter`](struct.Table.html#method.iter) starts on the first row.
+    /// This is useful for tables that misuse `<th>` for ordinary cells. Rows in
+    /// a `<thead>` are still used as headers.
+    pub fn treat_first_row_as_data(mut self, yes: bool) -> TableBuilder {
+        self.first_row_as_data = yes;
+        self
+    }
+
+    /// Sets the zero-based position of the header row among the rows of the
+    /// table outside its `<tfoot>`, instead of detecting it.
+    ///
+    /// The rows before it are skipped, and all of its cells, whether `<th>` or
+    /// `<td>`, become the headers, along with cells from earlier rows with a
+    /// `rowspan` reaching it. The table's rows start after it, and any
+    /// `<thead>` is treated like an ordinary group of rows. This is useful when
+    /// decorative rows, such as a title spanning all columns, come before the
+    /// real headers. If the table does not have that many rows, it has no
+    /// headers and all of its rows are kept.
+    ///
+    /// ```
+    /// # use table_extract::TableBuilder;
+    /// let html = r#"
+    /// <table>
+    ///     <tr><th colspan="2">People</th></tr>
+    ///     <tr><td>Name</td><td>Age</td></tr>
+    ///     <tr><td>John</td><td>20</td></tr>
+    /// </table>
+    /// "#;
+    /// let table = TableBuilder::new().header_row(1).find_first(html).unwrap();
+    /// assert_eq!(vec!["Name", "Age"], table.header_names());
+    /// assert_eq!(Some("20"), table.iter().next().unwrap().get("Age"));
+    /// ```
+    pub fn header_row(mut self, index: usize) -> TableBuilder {
+        self.header_row = Some(index);
+        self
+    }
+
+    /// Sets how many rows at the start of each table
+    /// [`find_by_headers`](#method.find_by_headers) searches for the headers.
+    /// The default is 1, which searches the first row only.
+    ///
+    /// This finds tables whose header row follows a title or spacer row. The
+    /// first of the rows outside the `<tfoot>` whose `<th>` cells contain all
+    /// of the headers becomes the [`header_row`](#method.header_row), so the
+    /// rows before it are skipped. Only the first `rows` rows of each table
+    /// are searched, so large tables without the headers are not scanned to
+    /// the end.
+    ///
+    /// ```
+    /// # use table_extract::TableBuilder;
+    /// let html = r#"
+    /// <table>
+    ///     <tr><th colspan="2">People</th></tr>
+    ///     <tr><th>Name</th><th>Age</th></tr>
+    ///     <tr><td>John</td><td>20</td></tr>
+    /// </table>
+    /// "#;
+    /// let options = TableBuilder::new().header_search_rows(3);
+    /// let table = options.find_by_headers(html, &["Age"]).unwrap();
+    /// assert_eq!(Some("20"), table.iter().next().unwrap().get("Age"));
+    /// ```
+    pub fn header_search_rows(mut self, rows: usize) -> TableBuilder {
+        self.header_search_rows = rows;
+        self
+    }
+
+    /// Sets whether to leave out rows that are hidden. This is disabled by
+    /// default.
+    ///
+    /// A row is hidden if its `<tr>` has a `hidden` attribute or an inline
+    /// `style` with `display: none`. Only the `<tr>` itself is checked, not
+    /// stylesheets or its ancestors. Hidden rows are also skipped when looking
+    /// for the header row, but still count towards the positions given by
+    /// [`Table::iter_with_source_row`].
+    ///
+    /// [`Table::iter_with_source_row`]:
+    /// struct.Table.html#method.iter_with_source_row
+    pub fn skip_hidden_rows(mut self, yes: bool) -> TableBuilder {
+        self.skip_hidden_rows = yes;
+        self
+    }
+
+    /// Sets whether to pad rows that are shorter than the header row with
+    /// empty cells. This is disabled by default.
+    ///
+    /// When enabled, each row covered by [`Table::iter`] or
+    /// [`Table::footer`] gets at least as many cells as there are columns
+    /// spanned by the headers, so [`Row::get`] finds a cell for every header.
+    /// The added cells are empty strings. Longer rows are left as they are,
+    /// and nothing is padded if the table has no headers.
+    ///
+    /// ```
+    /// # use table_extract::TableBuilder;
+    /// let html = r#"
+    /// <table>
+    ///     <tr><th>Name</th><th>Age</th></tr>
+    ///     <tr><td>John</td></tr>
+    /// </table>
+    /// "#;
+    /// let table = TableBuilder::new().pad_rows(true).find_first(html).unwrap();
+    /// assert_eq!(Some(""), table.iter().next().unwrap().get("Age"));
+    /// ```
+    ///
+    /// [`Table::iter`]: struct.Table.html#method.iter
+    /// [`Table::footer`]: struct.Table.html#method.footer
+    /// [`Row::get`]: struct.Row.html#method.get
+    pub fn pad_rows(mut self, yes: bool) -> TableBuilder {
+        self.pad_rows = yes;
+        self
+    }
+
+    /// Sets whether to keep the `data-*` attributes of each `<tr>` element,
+    /// for [`Row::data_attrs`](struct.Row.html#method.data_attrs). This is
+    /// disabled by default.
+    ///
+    /// The names and values of the attributes are stored for every row, which
+    /// adds their length in bytes plus about 48 bytes per attribute to the
+    /// memory used by the table. Rows keep their `id` and classes regardless
+    /// of this option.
+    pub fn row_data_attrs(mut self, yes: bool) -> TableBuilder {
+        self.row_data_attrs = yes;
+        self
+    }
+
+    /// Sets whether to name columns after the `<col>` elements in the table's
+    /// `<colgroup>`s when it has no header row. This is disabled by default.
+    ///
+    /// Each `<col>` is named by its `id`, or failing that its `class`, and
+    /// covers as many columns as its `span` attribute. Columns whose `<col>`
+    /// has neither are left without a header. A header row with `<th>` cells
+    /// always takes precedence.
+    ///
+    /// ```
+    /// # use table_extract::TableBuilder;
+    /// let html = r#"
+    /// <table>
+    ///     <colgroup><col id="name"><col class="age"></colgroup>
+    ///     <tr><td>John</td><td>20</td></tr>
+    /// </table>
+    /// "#;
+    /// let table = TableBuilder::new().col_names(true).find_first(html).unwrap();
+    /// let row = table.iter().next().unwrap();
+    /// assert_eq!(Some("20"), row.get("age"));
+    /// ```
+    pub fn col_names(mut self, yes: bool) -> TableBuilder {
+        self.col_names = yes;
+        self
+    }
+
+    /// Sets whether to normalize the header names used to look up cells, and
+    /// which characters to strip from their ends. This is disabled by default.
+    ///
+    /// When enabled, runs of whitespace in each name are collapsed into a
+    /// single space, and whitespace and the characters in `trailing` are
+    /// stripped from its end, so that footnote markers like `Age *` or `Age†`
+    /// can be looked up as `Age`. Pass `None` to disable it again.
+    ///
+    /// Only the lookup keys are affected, such as those used by
+    /// [`Row::get`](struct.Row.html#method.get) and returned by
+    /// [`Table::headers`](struct.Table.html#method.headers). The names returned
+    /// by [`Table::header_names`](struct.Table.html#method.header_names) are
+    /// left as they appear in the table.
+    ///
+    /// ```
+    /// # use table_extract::TableBuilder;
+    /// let html = r#"
+    /// <table>
+    ///     <tr><th>Full   name</th><th>Age *</th></tr>
+    ///     <tr><td>John</td><td>20</td></tr>
+    /// </table>
+    /// "#;
+    /// let table = TableBuilder::new()
+    ///     .normalize_headers(Some("*†"))
+    ///     .find_first(html)
+    ///     .unwrap();
+    /// let row = table.iter().next().unwrap();
+    /// assert_eq!(Some("John"), row.get("Full name"));
+    /// assert_eq!(Some("20"), row.get("Age"));
+    /// assert_eq!(vec!["Full   name", "Age *"], table.header_names());
+    /// ```
+    pub fn normalize_headers(mut self, trailing: Option<&str>) -> TableBuilder {
+        self.header_trailing = trailing.map(String::from);
+        self
+    }
+
+    /// Sets whether to match header names regardless of case and accents.
+    /// This is disabled by default.
+    ///
+    /// When enabled, header names are lowercased and common diacritics are
+    /// removed, both in the table and in the names passed to methods like
+    /// [`Row::get`](struct.Row.html#method.get), so `Ano` finds the column
+    /// headed `Año`. The lookup keys returned by
+    /// [`Table::headers`](struct.Table.html#method.headers) are folded as well,
+    /// but [`Table::header_names`](struct.Table.html#method.header_names) are
+    /// left as they appear in the table. The same goes for
+    /// [`Table::header_row`](struct.Table.html#method.header_row) and
+    /// [`Table::header_slice`](struct.Table.html#method.header_slice), and
+    /// [`find_by_headers`](#method.find_by_headers) still requires the
+    /// headers it searches for to match exactly.
+    ///
+    /// Folding uses a built-in table rather than full Unicode normalization.
+    /// It covers the letters of the Latin-1 Supplement and Latin Extended-A
+    /// blocks, which include those of most Western and Central European
+    /// languages, and removes combining diacritical marks (`U+0300` to
+    /// `U+036F`). Other scripts are only lowercased, and letters such as `ø`
+    /// and `ß` are replaced by their usual ASCII spellings (`o`, `ss`).
+    ///
+    /// ```
+    /// # use table_extract::TableBuilder;
+    /// let html = r#"
+    /// <table>
+    ///     <tr><th>Año</th><th>Café</th></tr>
+    ///     <tr><td>2020</td><td>Latte</td></tr>
+    /// </table>
+    /// "#;
+    /// let table = TableBuilder::new().ascii_fold(true).find_first(html).unwrap();
+    /// let row = table.iter().next().unwrap();
+    /// assert_eq!(Some("2020"), row.get("Ano"));
+    /// assert_eq!(Some("Latte"), row.get("CAFÉ"));
+    /// assert_eq!(vec!["Año", "Café"], table.header_names());
+    /// ```
+    pub fn ascii_fold(mut self, yes: bool) -> TableBuilder {
+        self.ascii_fold = yes;
+        self
+    }
+
+    /// Sets whether to name each column after all the header rows above it,
+    /// rather than the last one alone. This is disabled by default.
+    ///
+    /// When a table has several header rows, such as an `Address` header
+    /// spanning `Street` and `City` columns, the names from each row are
+    /// joined with the [`header_separator`](#method.header_separator), giving
+    /// distinct names like `Address / City`. Empty names are left out, as are
+    /// repeats of the name above, so a header spanning every header row keeps
+    /// its own name. The header rows are those in a `<thead>`, or those up to
+    /// the [`header_row`](#method.header_row) if it is set.
+    ///
+    /// ```
+    /// # use table_extract::TableBuilder;
+    /// let html = r#"
+    /// <table>
+    ///     <thead>
+    ///         <tr><th rowspan="2">Name</th><th colspan="2">Address</th></tr>
+    ///         <tr><th>Street</th><th>City</th></tr>
+    ///     </thead>
+    ///     <tr><td>John</td><td>Main St</td><td>Springfield</td></tr>
+    /// </table>
+    /// "#;
+    /// let table = TableBuilder::new()
+    ///     .combine_headers(true)
+    ///     .find_first(html)
+    ///     .unwrap();
+    /// assert_eq!(
+    ///     vec!["Name", "Address / Street", "Address / City"],
+    ///     table.header_names()
+    /// );
+    /// let row = table.iter().next().unwrap();
+    /// assert_eq!(Some("Springfield"), row.get("Address / City"));
+    /// ```
+    pub fn combine_headers(mut self, yes: bool) -> TableBuilder {
+        self.combine_headers = yes;
+        self
+    }
+
+    /// Sets the separator between the names of the header rows when
+    /// [`combine_headers`](#method.combine_headers) is enabled. The default is
+    /// `" / "`.
+    pub fn header_separator(mut self, separator: &str) -> TableBuilder {
+        self.header_separator = separator.to_string();
+        self
+    }
+
+    /// Sets the CSS selector for the cells of each row. The default is
+    /// `"th, td"`.
+    ///
+    /// The selector is matched against the child elements of each `<tr>`, so
+    /// it selects cells of the row itself rather than elements within them.
+    /// It applies to header and footer rows too, but whether a row is a
+    /// header row still depends on its `<th>` cells. The HTML parser moves
+    /// other elements out of table rows, so this is mostly useful for choosing
+    /// among `<th>` and `<td>` cells.
+    ///
+    /// Returns [`TableError::InvalidSelector`] if `selector` cannot be parsed.
+    ///
+    /// ```
+    /// # use table_extract::TableBuilder;
+    /// let html = r#"
+    /// <table>
+    ///     <tr><th>Tea</th><td>2.50</td></tr>
+    ///     <tr><th>Coffee</th><td>3.00</td></tr>
+    /// </table>
+    /// "#;
+    /// let table = TableBuilder::new()
+    ///     .cell_selector("td")
+    ///     .unwrap()
+    ///     .find_first(html)
+    ///     .unwrap();
+    /// assert_eq!(&["2.50"], table.iter().next().unwrap().as_slice());
+    /// ```
+    ///
+    /// [`TableError::InvalidSelector`]: enum.TableError.html#variant.InvalidSelector
+    pub fn cell_selector(
+        mut self,
+        selector: &str,
+    ) -> Result<TableBuilder, TableError> {
+        let parsed = Selector::parse(selector)
+            .map_err(|_| TableError::InvalidSelector(selector.to_string()))?;
+        self.cell_selector = Some(parsed);
+        Ok(self)
+    }
+
+    /// Finds the first table in `html` and parses it with these options.
+    ///
+    /// See [`Table::find_first`](struct.Table.html#method.find_first).
+    pub fn find_first(&self, html: &str) -> Option<Table> {
+        let html = Html::parse_fragment(html);
+        let table = html.root_element().select(&SEL_TABLE).next();
+        table.map(|e| self.build(e))
+    }
+
+    /// Finds all tables in `html` and parses them with these options.
+    ///
+    /// See [`Table::find_all`](struct.Table.html#method.find_all).
+    pub fn find_all(&self, html: &str) -> Vec<Table> {
+        let html = Html::parse_fragment(html);
+        let tables = html.root_element().select(&SEL_TABLE);
+        tables.map(|e| self.build(e)).collect()
+    }
+
+    /// Finds the table at the zero-based position `n` among all the tables in
+    /// `html` and parses it with these options.
+    ///
+    /// See [`Table::find_nth`](struct.Table.html#method.find_nth).
+    pub fn find_nth(&self, html: &str, n: usize) -> Option<Table> {
+        let html = Html::parse_fragment(html);
+        let table = html.root_element().select(&SEL_TABLE).nth(n);
+        table.map(|e| self.build(e))
+    }
+
+    /// Finds the table in `html` with an id of `id` and parses it with these
+    /// options.
+    ///
+    /// See [`Table::find_by_id`](struct.Table.html#method.find_by_id).
+    pub fn find_by_id(&self, html: &str, id: &str) -> Option<Table> {
+        let html = Html::parse_fragment(html);
+        let table = select_by_id(&html.root_element(), id).ok();
+        table.map(|e| self.build(e))
+    }
+
+    /// Finds the first table in `html` with a class of `class` and parses it
+    /// with these options.
+    ///
+    /// See [`Table::find_by_class`](struct.Table.html#method.find_by_class).
+    pub fn find_by_class(&self, html: &str, class: &str) -> Option<Table> {
+        let html = Html::parse_fragment(html);
+        let table = select_by_class(&html.root_element(), class);
+        table.map(|e| self.build(e))
+    }
+
+    /// Finds the first element in `html` matching the CSS selector `selector`
+    /// and parses it as a table with these options.
+    ///
+    /// See
+    /// [`Table::find_by_selector`](struct.Table.html#method.find_by_selector).
+    pub fn find_by_selector(
+        &self,
+        html: &str,
+        selector: &str,
+    ) -> Option<Table> {
+        let selector = Selector::parse(selector).ok()?;
+        let html = Html::parse_fragment(html);
+        let table = html.root_element().select(&selector).next();
+        table.map(|e| self.build(e))
+    }
+
+    /// Finds the table in `html` whose first row contains all of the headers
+    /// specified in `headers`, and parses it with these options.
+    ///
+    /// See [`Table::find_by_headers`](struct.Table.html#method.find_by_headers),
+    /// and [`header_search_rows`](#method.header_search_rows) for searching
+    /// more than the first row.
+    pub fn find_by_headers<T>(&self, html: &str, headers: &[T]) -> Option<Table>
+    where
+        T: AsRef<str>,
+    {
+        let html = Html::parse_fragment(html);
+        if self.header_search_rows <= 1 || headers.is_empty() {
+            let table = select_by_headers(&html.root_element(), headers);
+            return table.map(|(_, e)| self.build(e));
+        }
+        let mut tables = html.root_element().select(&SEL_TABLE);
+        let (table, row) = tables.find_map(|table| {
+            self.find_header_row(table, headers).map(|row| (table, row))
+        })?;
+        if row == 0 {
+            Some(self.build(table))
+        } else {
+            Some(self.clone().header_row(row).build(table))
+        }
+    }
+
+    /// Creates a table from a `<table>` element using these options.
+    pub fn build(&self, element: ElementRef) -> Table {
+        self.build_with(element, |cell| self.content(cell))
+    }
+
+    /// Creates a table from a `<table>` element using these options, but
+    /// extracting the contents of each cell with `f`.
+    ///
+    /// See [`Table::find_first_with`](struct.Table.html#method.find_first_with).
+    pub fn build_with<F>(&self, element: ElementRef, f: F) -> Table
+    where
+        F: Fn(ElementRef) -> String,
+    {
+        let sel_cell = self.sel_cell();
+        let sections = self.sections(element, sel_cell, &f);
+        let row_info = |(i, tr)| (RowInfo::new(i, tr, self), tr);
+        let (mut tr_info, rows): (Vec<_>, Vec<_>) =
+            sections.body.into_iter().map(row_info).unzip();
+        let (foot_info, foot): (Vec<_>, Vec<_>) =
+            sections.foot.into_iter().map(row_info).unzip();
+        tr_info.extend(foot_info);
+        let mut cells = Vec::new();
+        let mut data = layout_rows(rows, sel_cell, self, &f, &mut cells);
+        let mut footer = layout_rows(foot, sel_cell, self, &f, &mut cells);
+        if self.pad_rows {
+            let width = sections.headers.row.len();
+            let rows = data.iter_mut().chain(footer.iter_mut());
+            for (contents, info) in rows.zip(cells.iter_mut()) {
+                pad_row(contents, info, width);
+            }
+        }
+
+        let caption = child_elements(element, "caption").next().map(&f);
+
+        Table {
+            headers: sections.headers,
+            data,
+            footer,
+            caption,
+            cells,
+            rows: tr_info,
+            id: element.value().id().map(String::from),
+            classes: class_list(element),
+        }
+    }
+
+    /// Calls `f` with each row of the first table in `html`, parsed with these
+    /// options, without building the whole table.
+    ///
+    /// See [`Table::for_each_row`](struct.Table.html#method.for_each_row).
+    pub fn for_each_row<F>(&self, html: &str, mut f: F)
+    where
+        F: FnMut(Row<'_>),
+    {
+        let html = Html::parse_fragment(html);
+        let table = match html.root_element().select(&SEL_TABLE).next() {
+            Some(table) => table,
+            None => return,
+        };
+        let sel_cell = self.sel_cell();
+        let content = |cell: ElementRef| self.content(cell);
+        let sections = self.sections(table, sel_cell, &content);
+        let mut spans = RowSpans::new(self.span_fill);
+        for (i, tr) in sections.body {
+            let slots = spans.layout(child_cells(tr, sel_cell), self, &content);
+            let (mut cells, mut info): (Vec<_>, Vec<_>) =
+                slots.into_iter().map(Slot::into_parts).unzip();
+            if self.pad_rows {
+                pad_row(&mut cells, &mut info, sections.headers.row.len());
+            }
+            f(Row {
+                headers: &sections.headers,
+                cells: &cells,
+                info: &info,
+                tr: &RowInfo::new(i, tr, self),
+            });
+        }
+    }
+
+    /// Returns the position of the first row among the first
+    /// `header_search_rows` of `table` outside its `<tfoot>` whose `<th>` cells
+    /// contain all of `headers`, counting rows as
+    /// [`header_row`](#method.header_row) does.
+    fn find_header_row<T>(
+        &self,
+        table: ElementRef,
+        headers: &[T],
+    ) -> Option<usize>
+    where
+        T: AsRef<str>,
+    {
+        table
+            .select(&SEL_TR)
+            .filter(|&tr| !is_nested(tr, table))
+            .filter(|&tr| !(self.skip_hidden_rows && is_hidden(tr)))
+            .filter(|&tr| !parent_is(tr, "tfoot"))
+            .take(self.header_search_rows)
+            .position(|tr| {
+                let cells = select_cells(tr, &SEL_TH);
+                headers.iter().all(|h| contains_str(&cells, h.as_ref()))
+            })
+    }
+
+    /// Returns the selector for the cells of each row.
+    fn sel_cell(&self) -> &Selector {
+        self.cell_selector.as_ref().unwrap_or(&SEL_CELL)
+    }
+
+    /// Sorts the rows of a `<table>` element into sections, and builds its
+    /// headers from the header rows.
+    fn sections<'a>(
+        &self,
+        element: ElementRef<'a>,
+        sel_cell: &Selector,
+        content: &dyn Fn(ElementRef) -> String,
+    ) -> Sections<'a> {
+        let (foot, rest): (Vec<_>, Vec<_>) = element
+            .select(&SEL_TR)
+            .filter(|&tr| !is_nested(tr, element))
+            .enumerate()
+            .filter(|&(_, tr)| !(self.skip_hidden_rows && is_hidden(tr)))
+            .partition(|&(_, tr)| parent_is(tr, "tfoot"));
+        let (head, body): (Vec<_>, Vec<_>) = match self.header_row {
+            Some(index) if index < rest.len() => {
+                let mut rest = rest;
+                let body = rest.split_off(index + 1);
+                (rest, body)
+            }
+            Some(_) => (Vec::new(), rest),
+            None => rest
+                .into_iter()
+                .partition(|&(_, tr)| parent_is(tr, "thead")),
+        };
+        // A <thead> without any <th> cells holds data rows, which come before
+        // the row that might be the header.
+        let has_th = |&(_, tr): &(usize, ElementRef)| {
+            child_cells(tr, &SEL_TH).next().is_some()
+        };
+        let (head, leading) =
+            if self.header_row.is_none() && !head.iter().any(has_th) {
+                (Vec::new(), head)
+            } else {
+                (head, Vec::new())
+            };
+        let mut headers = HeaderInfo::default();
+        let mut rows = body.into_iter().peekable();
+        if !head.is_empty() {
+            let mut spans = RowSpans::new(SpanFill::Duplicate);
+            let mut grid: Vec<_> = head
+                .into_iter()
+                .map(|(_, tr)| {
+                    spans.layout(child_cells(tr, sel_cell), self, content)
+                })
+                .collect();
+            let mut last = grid.pop().unwrap_or_default();
+            if self.combine_headers {
+                combine_names(&mut last, &grid, &self.header_separator);
+            }
+            headers = header_map(last);
+        } else if let Some(&(_, tr)) = rows.peek() {
+            if !self.first_row_as_data
+                && self.header_row.is_none()
+                && is_header_row(tr)
+            {
+                let mut spans = RowSpans::new(SpanFill::Duplicate);
+                let slots =
+                    spans.layout(child_cells(tr, sel_cell), self, content);
+                headers = header_map(slots);
+                rows.next();
+            }
+        }
+        if headers.names.is_empty() && self.col_names {
+            headers = col_headers(element);
+        }
+        if self.header_trailing.is_some() || self.ascii_fold {
+            headers.map.clear();
+            headers.ascii_fold = self.ascii_fold;
+            headers.keys.clear();
+            for (name, &i) in headers.names.iter().zip(&headers.columns) {
+                let key = match &self.header_trailing {
+                    Some(trailing) => normalize_key(name, trailing),
+                    None => name.clone(),
+                };
+                let key = if self.ascii_fold {
+                    fold_header(&key)
+                } else {
+                    key
+                };
+                headers.map.entry(key.clone()).or_insert(i);
+                headers.keys.push(key);
+            }
+        }
+        Sections {
+            headers,
+            body: leading.into_iter().chain(rows).collect(),
+            foot,
+        }
+    }
+
+    /// Extracts the contents of a cell.
+    fn content(&self, element: ElementRef) -> String {
+        self.extract(element, self.mode)
+    }
+
+    /// Extracts the contents of a cell using `mode` instead of the configured
+    /// text mode.
+    fn extract(&self, element: ElementRef, mode: TextMode) -> String {
+        match mode {
+            TextMode::Html => self.clean(&element.inner_html(), mode),
+            TextMode::Text if !self.line_breaks => {
+                self.clean(&element.text().collect::<String>(), mode)
+            }
+            TextMode::Text => {
+                let lines = text_lines(element);
+                let lines: Vec<_> =
+                    lines.iter().map(|l| self.clean(l, mode)).collect();
+                let text = lines.join("\n");
+                if self.trim {
+                    trim_cell(&text, mode).to_string()
+                } else {
+                    text
+                }
+            }
+        }
+    }
+
+    /// Trims and collapses whitespace in extracted content according to the
+    /// options.
+    fn clean(&self, content: &str, mode: TextMode) -> String {
+        let content = if self.trim {
+            trim_cell(content, mode)
+        } else {
+            content
+        };
+        match self.whitespace {
+            Whitespace::Preserve => content.to_string(),
+            Whitespace::Collapse => collapse_whitespace(content),
+        }
+    }
+}
+
+/// Options for telling tables of data apart from tables used for page layout.
+///
+/// Layout tables usually have a single row, or no `<th>` cells and a single
+/// column, with the page content nested inside them. A table is treated as a
+/// data table if it has at least [`min_rows`](#method.min_rows) rows, and
+/// either has a `<th>` cell or at least
+/// [`min_columns`](#method.min_columns) cells in some row. Rows and cells of
+/// nested tables are not counted, so a data table nested in a layout table is
+/// still found.
+///
+/// ```
+/// # use table_extract::DataTableFilter;
+/// let html = r#"
+///     <table><tr><td>Navigation</td></tr></table>
+///     <table>
+///         <tr><td>John</td></tr>
+///         <tr><td>May</td></tr>
+///     </table>
+/// "#;
+/// let table = DataTableFilter::new().min_columns(1).find_first(html).unwrap();
+/// assert_eq!(Some("May"), table.cell(1, 0));
+/// ```
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub struct DataTableFilter {
+    min_rows: usize,
+    min_columns: usize,
+}
+
+impl Default for DataTableFilter {
+    fn default() -> DataTableFilter {
+        DataTableFilter {
+            min_rows: 2,
+            min_columns: 2,
+        }
+    }
+}
+
+impl DataTableFilter {
+    /// Creates a filter with the default thresholds.
+    pub fn new() -> DataTableFilter {
+        DataTableFilter::default()
+    }
+
+    /// Sets the fewest rows a data table can have, including its header row.
+    /// The default is 2.
+    pub fn min_rows(mut self, rows: usize) -> DataTableFilter {
+        self.min_rows = rows;
+        self
+    }
+
+    /// Sets the fewest cells a data table without `<th>` cells must have in
+    /// its widest row. The default is 2.
+    pub fn min_columns(mut self, columns: usize) -> DataTableFilter {
+        self.min_columns = columns;
+        self
+    }
+
+    /// Returns true if the `<table>` element `table` looks like a table of
+    /// data rather than one used for layout.
+    pub fn is_data_table(&self, table: ElementRef) -> bool {
+        let rows: Vec<_> = table
+            .select(&SEL_TR)
+            .filter(|&tr| !is_nested(tr, table))
+            .collect();
+        if rows.len() < self.min_rows {
+            return false;
+        }
+        let cells = rows.iter().map(|&tr| child_cells(tr, &SEL_CELL));
+        let mut columns = 0;
+        for row in cells {
+            let mut count = 0;
+            for cell in row {
+                if cell.value().name() == "th" {
+                    return true;
+                }
+                count += 1;
+            }
+            columns = columns.max(count);
+        }
+        columns >= self.min_columns
+    }
+
+    /// Finds the first data table in `html`.
+    ///
+    /// See [`Table::find_first_data_table`](struct.Table.html#method.find_first_data_table).
+    pub fn find_first(&self, html: &str) -> Option<Table> {
+        let html = Html::parse_fragment(html);
+        self.find_first_from_elem(&html.root_element())
+    }
+
+    /// Finds the first data table within `element`.
+    pub fn find_first_from_elem(&self, element: &ElementRef) -> Option<Table> {
+        let mut tables = element.select(&SEL_TABLE);
+        tables
+            .find(|&table| self.is_data_table(table))
+            .map(Table::new)
+    }
+}
+
+/// Finds the first table in a parsed HTML document or fragment, like
+/// [`Table::find_first_from_html`](struct.Table.html#method.find_first_from_html).
+///
+/// Returns [`TableError::NoTableFound`] if there is no table.
+///
+/// ```
+/// # use scraper::Html;
+/// # use table_extract::{Table, TableError};
+/// use std::convert::TryFrom;
+///
+/// let html = Html::parse_fragment("<table><tr><td>1</td></tr></table>");
+/// let table = Table::try_from(&html).unwrap();
+/// assert_eq!(Some("1"), table.cell(0, 0));
+///
+/// let html = Html::parse_fragment("<p>No table</p>");
+/// assert_eq!(Err(TableError::NoTableFound), Table::try_from(&html));
+/// ```
+///
+/// [`TableError::NoTableFound`]: enum.TableError.html#variant.NoTableFound
+impl TryFrom<&Html> for Table {
+    type Error = TableError;
+
+    fn try_from(html: &Html) -> Result<Table, TableError> {
+        Table::find_first_from_html(html).ok_or(TableError::NoTableFound)
+    }
+}
+
+/// Creates a table from `element` if it is a `<table>`, and otherwise finds the
+/// first table that is a descendant of it, like
+/// [`Table::find_first_from_elem`](struct.Table.html#method.find_first_from_elem).
+///
+/// Returns [`TableError::NoTableFound`] if there is no table.
+///
+/// [`TableError::NoTableFound`]: enum.TableError.html#variant.NoTableFound
+impl<'a> TryFrom<ElementRef<'a>> for Table {
+    type Error = TableError;
+
+    fn try_from(element: ElementRef<'a>) -> Result<Table, TableError> {
+        if element.value().name() == "table" {
+            return Ok(Table::new(element));
+        }
+        Table::find_first_from_elem(&element).ok_or(TableError::NoTableFound)
+    }
+}
+
+/// Prints the table as [`to_ascii`](struct.Table.html#method.to_ascii) does,
+/// which is easier to read than the `Debug` output.
+///
+/// ```
+/// # use table_extract::Table;
+/// let html = "<table><tr><th>Name</th></tr><tr><td>John</td></tr></table>";
+/// let table = Table::find_first(html).unwrap();
+/// assert_eq!(table.to_ascii(), table.to_string());
+/// ```
+impl fmt::Display for Table {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.to_ascii())
+    }
+}
+
+impl<'a> IntoIterator for &'a Table {
+    type Item = Row<'a>;
+    type IntoIter = Iter<'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// Consumes the table, yielding [`OwnedRow`](struct.OwnedRow.html)s.
+///
+/// This yields the same rows as [`iter`](#method.iter), but each one owns its
+/// cells rather than borrowing them from the table, so it can outlive the
+/// table or be sent to another thread. The headers are shared by all the rows
+/// rather than being cloned for each one.
+impl IntoIterator for Table {
+    type Item = OwnedRow;
+    type IntoIter = IntoIter;
+
+    fn into_iter(self) -> Self::IntoIter {
+        IntoIter {
+            headers: Arc::new(self.headers),
+            iter: self.data.into_iter(),
+            cells: self.cells.into_iter(),
+            rows: self.rows.into_iter(),
+        }
+    }
+}
+
+/// An iterator over the rows in a [`Table`](struct.Table.html).
+pub struct Iter<'a> {
+    headers: &'a HeaderInfo,
+    iter: std::slice::Iter<'a, Vec<String>>,
+    cells: std::slice::Iter<'a, Vec<Cell>>,
+    rows: std::slice::Iter<'a, RowInfo>,
+}
+
+impl<'a> Iterator for Iter<'a> {
+    type Item = Row<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let headers = self.headers;
+        let info = self.cells.next().map_or(&[][..], Vec::as_slice);
+        let tr = self.rows.next().unwrap_or(&NO_ROW);
+        self.iter.next().map(|cells| Row {
+            headers,
+            cells,
+            info,
+            tr,
+        })
+    }
+}
+
+/// An owning iterator over the rows in a [`Table`](struct.Table.html).
+pub struct IntoIter {
+    headers: Arc<HeaderInfo>,
+    iter: std::vec::IntoIter<Vec<String>>,
+    cells: std::vec::IntoIter<Vec<Cell>>,
+    rows: std::vec::IntoIter<RowInfo>,
+}
+
+impl Iterator for IntoIter {
+    type Item = OwnedRow;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let info = self.cells.next().unwrap_or_default();
+        let tr = self.rows.next().unwrap_or_default();
+        self.iter.next().map(|cells| OwnedRow {
+            headers: Arc::clone(&self.headers),
+            cells,
+            info,
+            tr,
+        })
+    }
+}
+
+/// A row that owns its cells, obtained by consuming a
+/// [`Table`](struct.Table.html).
+///
+/// Use [`as_row`](#method.as_row) to access its contents through the methods
+/// of [`Row`](struct.Row.html). Like `Row`, it compares and hashes by the
+/// contents of its cells only.
+#[derive(Clone, Debug)]
+pub struct OwnedRow {
+    headers: Arc<HeaderInfo>,
+    cells: Vec<String>,
+    info: Vec<Cell>,
+    tr: RowInfo,
+}
+
+impl OwnedRow {
+    /// Returns a [`Row`](struct.Row.html) borrowing from this one.
+    pub fn as_row(&self) -> Row<'_> {
+        Row {
+            headers: &self.headers,
+            cells: &self.cells,
+            info: &self.info,
+            tr: &self.tr,
+        }
+    }
+
+    /// Returns the cell underneath `header`. See [`Row::get`].
+    ///
+    /// [`Row::get`]: struct.Row.html#method.get
+    pub fn get(&self, header: &str) -> Option<&str> {
+        self.as_row().get(header)
+    }
+
+    /// Returns a slice containing all the cells.
+    pub fn as_slice(&self) -> &[String] {
+        &self.cells
+    }
+
+    /// Consumes the row, returning its cells.
+    pub fn into_vec(self) -> Vec<String> {
+        self.cells
+    }
+}
+
+impl PartialEq for OwnedRow {
+    fn eq(&self, other: &OwnedRow) -> bool {
+        self.cells == other.cells
+    }
+}
+
+impl Eq for OwnedRow {}
+
+impl Hash for OwnedRow {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.cells.hash(state);
+    }
+}
+
+/// A row in a [`Table`](struct.Table.html).
+///
+/// A row consists of a number of data cells stored as strings. If the row
+/// contains the same number of cells as the table's header row, its cells can
+/// be safely accessed by header names using [`get`](#method.get). Otherwise,
+/// the data should be accessed via [`as_slice`](#method.as_slice) or by
+/// iterating over the row.
+///
+/// This struct can be thought of as a lightweight reference into a table. As
+/// such, it implements the `Copy` trait. With the `serde` feature enabled, it
+/// serializes as a sequence of its cells.
+///
+/// Rows compare and hash by the contents of their cells only, like slices of
+/// strings. The headers and other information about the cells do not
+/// participate, so equal rows from different tables can be deduplicated with
+/// a `HashSet`.
+#[derive(Clone, Copy, Debug)]
+pub struct Row<'a> {
+    headers: &'a HeaderInfo,
+    cells: &'a [String],
+    info: &'a [Cell],
+    tr: &'a RowInfo,
+}
+
+impl<'a> PartialEq for Row<'a> {
+    fn eq(&self, other: &Row<'a>) -> bool {
+        self.cells == other.cells
+    }
+}
+
+impl<'a> Eq for Row<'a> {}
+
+impl<'a> Hash for Row<'a> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.cells.hash(state);
+    }
+}
+
+impl<'a> Row<'a> {
+    /// Returns the number of cells in the row.
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    /// Returns `true` if the row contains no cells.
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    /// Returns the cell underneath `header`.
+    ///
+    /// Returns `None` if there is no such header, or if there is no cell at
+    /// that position in the row. If several columns have the same header, the
+    /// first one is used; see [`get_all`](#method.get_all) for the others.
+    pub fn get(&self, header: &str) -> Option<&'a str> {
+        self.headers.index(header).and_then(|i| self.get_index(i))
+    }
+
+    /// Like [`get`](#method.get), but returns a copy of the cell that does
+    /// not borrow from the table.
+    ///
+    /// ```
+    /// # use table_extract::Table;
+    /// let html = r#"
+    ///     <table>
+    ///         <tr><th>Name</th></tr>
+    ///         <tr><td>John</td></tr>
+    ///     </table>
+    /// "#;
+    /// let names: Vec<String> = Table::find_first(html)
+    ///     .unwrap()
+    ///     .iter()
+    ///     .filter_map(|row| row.get_owned("Name"))
+    ///     .collect();
+    /// assert_eq!(vec!["John"], names);
+    /// ```
+    pub fn get_owned(&self, header: &str) -> Option<String> {
+        self.get(header).map(String::from)
+    }
+
+    /// Like [`get`](#method.get), but returns `None` if the cell is empty.
+    ///
+    /// This is useful when empty cells mean missing data. See also
+    /// [`get_trimmed`](#method.get_trimmed).
+    pub fn get_nonempty(&self, header: &str) -> Option<&'a str> {
+        self.get(header).filter(|cell| !cell.is_empty())
+    }
+
+    /// Like [`get`](#method.get), but removes leading and trailing whitespace
+    /// from the cell, and returns `None` if nothing is left.
+    ///
+    /// This is useful for tables parsed without
+    /// [`trim`](struct.TableBuilder.html#method.trim).
+    pub fn get_trimmed(&self, header: &str) -> Option<&'a str> {
+        self.get(header)
+            .map(str::trim)
+            .filter(|cell| !cell.is_empty())
+    }
+
+    /// Like [`get`](#method.get), but compares header names
+    /// case-insensitively.
+    ///
+    /// If several headers differ only in case, the leftmost one is used.
+    pub fn get_ci(&self, header: &str) -> Option<&'a str> {
+        let key = |h: &str| self.headers.key(h).to_lowercase();
+        let header = key(header);
+        self.find_header(|h| key(h) == header)
+    }
+
+    /// Like [`get`](#method.get), but ignores leading and trailing whitespace
+    /// and case in header names, both in the table and in `header`.
+    ///
+    /// If several headers are the same after normalizing, the leftmost one is
+    /// used.
+    ///
+    /// ```
+    /// # use table_extract::Table;
+    /// let html = "<table><tr><th>Name </th></tr><tr><td>John</td></tr></table>";
+    /// let table = Table::find_first(html).unwrap();
+    /// let row = table.iter().next().unwrap();
+    /// assert_eq!(None, row.get("name"));
+    /// assert_eq!(Some("John"), row.get_normalized(" name"));
+    /// ```
+    pub fn get_normalized(&self, header: &str) -> Option<&'a str> {
+        let key = |h: &str| normalize_header(&self.headers.key(h));
+        let header = key(header);
+        self.find_header(|h| key(h) == header)
+    }
+
+    /// Returns the cell underneath the leftmost header whose key `matches`
+    /// returns true for.
+    fn find_header<F>(&self, matches: F) -> Option<&'a str>
+    where
+        F: Fn(&str) -> bool,
+    {
+        let mut found = self.headers.keyed().filter(|(h, _)| matches(h));
+        found.next().and_then(|(_, i)| self.get_index(i))
+    }
+
+    /// Returns the `href` targets of the links in the cell underneath
+    /// `header`, in document order.
+    ///
+    /// Returns an empty vector if there is no such header or cell, or if the
+    /// cell contains no links.
+    pub fn get_links(&self, header: &str) -> Vec<&'a str> {
+        self.cell(header).map_or_else(Vec::new, Cell::links)
+    }
+
+    /// Returns the inner HTML of the cell underneath `header`, regardless of
+    /// the [`TextMode`](enum.TextMode.html) used to parse the table.
+    pub fn get_html(&self, header: &str) -> Option<&'a str> {
+        self.cell(header).map(Cell::html)
+    }
+
+    /// Returns the text of the cell underneath `header`, regardless of the
+    /// [`TextMode`](enum.TextMode.html) used to parse the table.
+    pub fn get_text(&self, header: &str) -> Option<&'a str> {
+        self.cell(header).map(Cell::text)
+    }
+
+    /// Returns the value of the attribute `name` on the cell underneath
+    /// `header`.
+    ///
+    /// ```
+    /// # use table_extract::Table;
+    /// let html = r#"
+    /// <table>
+    ///     <tr><th>Size</th></tr>
+    ///     <tr><td data-sort-value="1024">1 KiB</td></tr>
+    /// </table>
+    /// "#;
+    /// let table = Table::find_first(html).unwrap();
+    /// let row = table.iter().next().unwrap();
+    /// assert_eq!(Some("1024"), row.get_attr("Size", "data-sort-value"));
+    /// ```
+    pub fn get_attr(&self, header: &str, name: &str) -> Option<&'a str> {
+        self.cell(header)?.attr(name)
+    }
+
+    /// Returns information about the cell underneath `header`.
+    ///
+    /// Returns `None` if there is no such header, or if there is no cell at
+    /// that position in the row.
+    pub fn cell(&self, header: &str) -> Option<&'a Cell> {
+        let index = self.headers.index(header)?;
+        self.cell_index(index)
+    }
+
+    /// Returns information about the cell at the zero-based position `index`.
+    pub fn cell_index(&self, index: usize) -> Option<&'a Cell> {
+        self.info.get(index)
+    }
+
+    /// Returns the span information of the cell at the zero-based position
+    /// `index`. See [`Cell::span`](struct.Cell.html#method.span).
+    ///
+    /// ```
+    /// # use table_extract::{CellSpan, Table};
+    /// let html = r#"
+    /// <table>
+    ///     <tr><th>Group</th><th>Amount</th></tr>
+    ///     <tr><td>A</td><td rowspan="2">10</td></tr>
+    ///     <tr><td>B</td></tr>
+    /// </table>
+    /// "#;
+    /// let table = Table::find_first(html).unwrap();
+    /// let total: u32 = table
+    ///     .iter()
+    ///     .filter(|row| row.span_index(1) == Some(CellSpan::Origin))
+    ///     .map(|row| row.parse::<u32>("Amount").unwrap().unwrap())
+    ///     .sum();
+    /// assert_eq!(10, total);
+    /// ```
+    pub fn span_index(&self, index: usize) -> Option<CellSpan> {
+        self.cell_index(index).map(Cell::span)
+    }
+
+    /// Returns the row header, if the row begins with a `<th>` cell.
+    ///
+    /// Row headers are common in comparison tables, where the first cell of
+    /// each row labels it. They are also included in the row's data like any
+    /// other cell, so they can be looked up by the column header above them.
+    ///
+    /// ```
+    /// # use table_extract::Table;
+    /// let html = r#"
+    /// <table>
+    ///     <tr><th></th><th>Speed</th></tr>
+    ///     <tr><th>Car</th><td>Fast</td></tr>
+    /// </table>
+    /// "#;
+    /// let table = Table::find_first(html).unwrap();
+    /// let row = table.iter().next().unwrap();
+    /// assert_eq!(Some("Car"), row.row_header());
+    /// assert_eq!(Some("Fast"), row.get("Speed"));
+    /// ```
+    pub fn row_header(&self) -> Option<&'a str> {
+        match (self.cells.first(), self.info.first()) {
+            (Some(content), Some(cell)) if cell.is_header() => Some(content),
+            _ => None,
+        }
+    }
+
+    /// Returns the `id` attribute of the row's `<tr>` element, if it has one.
+    ///
+    /// Rows that do not come from a `<tr>` element, such as those of a
+    /// [transposed](struct.Table.html#method.transpose) table or the
+    /// [header row](struct.Table.html#method.header_row), have no `id`.
+    ///
+    /// ```
+    /// # use table_extract::Table;
+    /// let html = r#"
+    /// <table>
+    ///     <tr><th>Name</th></tr>
+    ///     <tr id="row-1" class="person active"><td>John</td></tr>
+    /// </table>
+    /// "#;
+    /// let table = Table::find_first(html).unwrap();
+    /// let row = table.iter().next().unwrap();
+    /// assert_eq!(Some("row-1"), row.id());
+    /// assert_eq!(vec!["person", "active"], row.classes());
+    /// ```
+    pub fn id(&self) -> Option<&'a str> {
+        self.tr.id.as_deref()
+    }
+
+    /// Returns the classes of the row's `<tr>` element, in the order they
+    /// appear in its `class` attribute.
+    pub fn classes(&self) -> Vec<&'a str> {
+        self.tr.classes.iter().map(String::as_str).collect()
+    }
+
+    /// Returns a map from the name of each `data-*` attribute of the row's
+    /// `<tr>` element to its value.
+    ///
+    /// The names include the `data-` prefix, as in the HTML. The map is empty
+    /// unless the table was parsed with
+    /// [`TableBuilder::row_data_attrs`](struct.TableBuilder.html#method.row_data_attrs)
+    /// enabled, since keeping the attributes uses extra memory for every row.
+    ///
+    /// ```
+    /// # use table_extract::TableBuilder;
+    /// let html = r#"
+    /// <table>
+    ///     <tr><th>Name</th></tr>
+    ///     <tr data-id="42" data-status="active"><td>John</td></tr>
+    /// </table>
+    /// "#;
+    /// let table = TableBuilder::new()
+    ///     .row_data_attrs(true)
+    ///     .find_first(html)
+    ///     .unwrap();
+    /// let attrs = table.iter().next().unwrap().data_attrs();
+    /// assert_eq!(Some(&"42"), attrs.get("data-id"));
+    /// assert_eq!(Some(&"active"), attrs.get("data-status"));
+    /// ```
+    pub fn data_attrs(&self) -> HashMap<&'a str, &'a str> {
+        let attrs = self.tr.data_attrs.iter();
+        attrs
+            .map(|(name, value)| (name.as_str(), value.as_str()))
+            .collect()
+    }
+
+    /// Parses the cell underneath `header` as a `T`.
+    ///
+    /// Returns `None` if there is no such header or cell, and `Some(Err(_))`
+    /// if the cell could not be parsed. For example:
+    ///
+    /// ```
+    /// let html = r#"
+    ///     <table>
+    ///         <tr><th>Name</th><th>Age</th></tr>
+    ///         <tr><td>John</td><td>20</td></tr>
+    ///     </table>
+    /// "#;
+    /// let table = table_extract::Table::find_first(html).unwrap();
+    /// let row = table.iter().next().unwrap();
+    /// assert_eq!(Some(Ok(20)), row.parse::<u32>("Age"));
+    /// assert!(row.parse::<u32>("Name").unwrap().is_err());
+    /// assert_eq!(None, row.parse::<u32>("Weight"));
+    /// ```
+    pub fn parse<T>(&self, header: &str) -> Option<Result<T, T::Err>>
+    where
+        T: FromStr,
+    {
+        self.get(header).map(str::parse)
+    }
+
+    /// Parses the cell underneath `header` as a number, ignoring the
+    /// formatting commonly used for amounts of money.
+    ///
+    /// Before parsing, the currency symbols `$`, `€`, `£`, and `¥`, commas
+    /// used as thousands separators, and whitespace are removed. A value in
+    /// parentheses, as in accounting, is negative, and must not have a sign of
+    /// its own. Returns `None` if there is no such header or cell, or if what
+    /// remains is not a finite number, so `inf` and `NaN` are rejected. Commas
+    /// are always treated as thousands separators, never as decimal points.
+    ///
+    /// ```
+    /// # use table_extract::Table;
+    /// let html = r#"
+    ///     <table>
+    ///         <tr><th>Item</th><th>Amount</th></tr>
+    ///         <tr><td>Rent</td><td>$1,234.50</td></tr>
+    ///         <tr><td>Refund</td><td>(£20)</td></tr>
+    ///     </table>
+    /// "#;
+    /// let table = Table::find_first(html).unwrap();
+    /// let amounts: Vec<_> =
+    ///     table.iter().map(|row| row.parse_number("Amount")).collect();
+    /// assert_eq!(vec![Some(1234.5), Some(-20.0)], amounts);
+    /// ```
+    pub fn parse_number(&self, header: &str) -> Option<f64> {
+        self.get(header).and_then(parse_number)
+    }
+
+    /// Like [`get`](#method.get), but returns a
+    /// [`RowError`](enum.RowError.html) if there is no such header or cell.
+    pub fn try_get(&self, header: &str) -> Result<&'a str, RowError> {
+        self.get(header)
+            .ok_or_else(|| RowError::MissingColumn(header.to_string()))
+    }
+
+    /// Like [`parse`](#method.parse), but returns a
+    /// [`RowError`](enum.RowError.html) if there is no such header or cell, or
+    /// if the cell could not be parsed.
+    pub fn try_parse<T>(&self, header: &str) -> Result<T, RowError>
+    where
+        T: FromStr,
+        T::Err: fmt::Display,
+    {
+        let value = self.try_get(header)?;
+        value.parse().map_err(|err: T::Err| RowError::InvalidValue {
+            header: header.to_string(),
+            value: value.to_string(),
+            message: err.to_string(),
+        })
+    }
+
+    /// Returns the cell at the zero-based position `index`.
+    ///
+    /// Returns `None` if the row has no cell at that position.
+    pub fn get_index(&self, index: usize) -> Option<&'a str> {
+        self.cells.get(index).map(String::as_str)
+    }
+
+    /// Returns the cells underneath every header named `header`, in column
+    /// order.
+    ///
+    /// This is useful when several columns have the same header, since
+    /// [`get`](#method.get) only returns the first of them. Headers without a
+    /// cell in this row are skipped.
+    pub fn get_all(&self, header: &str) -> Vec<&'a str> {
+        let header = self.headers.key(header);
+        self.headers
+            .keyed()
+            .filter(|(key, _)| **key == header)
+            .filter_map(|(_, i)| self.get_index(i))
+            .collect()
+    }
+
+    /// Returns an iterator over the cells of the row that have a header, each
+    /// paired with the name of its header, in column order.
+    ///
+    /// A header spanning several columns names each of them, and a repeated
+    /// header names each of its columns, unlike with [`get`](#method.get).
+    /// Cells beyond the header row, or under an empty header, are skipped.
+    /// The names are the keys of [`headers`](#method.headers), so they are
+    /// normalized or folded if the table's headers were.
+    ///
+    /// ```
+    /// # use table_extract::Table;
+    /// let html = r#"
+    ///     <table>
+    ///         <tr><th>Name</th><th>Age</th></tr>
+    ///         <tr><td>John</td><td>20</td><td>extra</td></tr>
+    ///     </table>
+    /// "#;
+    /// let table = Table::find_first(html).unwrap();
+    /// let row = table.iter().next().unwrap();
+    /// let named: Vec<_> = row.iter_named().collect();
+    /// assert_eq!(vec![("Name", "John"), ("Age", "20")], named);
+    /// ```
+    pub fn iter_named(&self) -> impl Iterator<Item = (&'a str, &'a str)> + 'a {
+        let headers = self.headers;
+        let names = headers.row.iter().enumerate().map(move |(i, name)| {
+            // A column is named by the last header starting at or before it.
+            let k = headers.columns.partition_point(|&col| col <= i);
+            match k.checked_sub(1).and_then(|k| headers.keys.get(k)) {
+                Some(key) if !name.is_empty() => key.as_str(),
+                _ => "",
+            }
+        });
+        names
+            .zip(self.cells.iter().map(String::as_str))
+            .filter(|(name, _)| !name.is_empty())
+    }
+
+    /// Returns a map from each header to the cell underneath it.
+    ///
+    /// Headers without a cell in this row are omitted, so the map is empty if
+    /// the table has no headers.
+    pub fn to_map(&self) -> HashMap<&'a str, &'a str> {
+        self.headers
+            .map
+            .iter()
+            .filter_map(|(h, &i)| self.get_index(i).map(|c| (h.as_str(), c)))
+            .collect()
+    }
+
+    /// Returns a slice containing all the cells.
+    pub fn as_slice(&self) -> &'a [String] {
+        self.cells
+    }
+
+    /// Returns an iterator over the cells of the row.
+    pub fn iter(&self) -> std::slice::Iter<'_, String> {
+        self.cells.iter()
+    }
+}
+
+impl<'a> IntoIterator for Row<'a> {
+    type Item = &'a String;
+    type IntoIter = std::slice::Iter<'a, String>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.cells.iter()
+    }
+}
+
+/// A table whose cells borrow their text from a parsed HTML document instead
+/// of owning a copy of it.
+///
+/// A [`Table`](struct.Table.html) allocates a `String` for every cell. When
+/// the parsed [`Html`] outlives the table and the table is only read, a
+/// `BorrowedTable` avoids most of these allocations: a cell whose text comes
+/// from a single text node, like `<td>John</td>` or `<td> <b>20</b> </td>`,
+/// borrows it from the document. Only cells whose text is split across
+/// several nodes, such as `<td>a <b>b</b></td>`, are copied.
+///
+/// Cells are extracted as with
+/// [`TextMode::Text`](enum.TextMode.html#variant.Text) and the other default
+/// options, except that `rowspan` and `colspan` are not expanded, as with
+/// [`TableBuilder::expand_spans(false)`](struct.TableBuilder.html#method.expand_spans).
+/// The rows are those covered by [`Table::iter`](struct.Table.html#method.iter),
+/// so the `<tfoot>` is not included.
+///
+/// ```
+/// use scraper::Html;
+/// use table_extract::BorrowedTable;
+///
+/// let html = Html::parse_fragment(
+///     r#"
+///     <table>
+///         <tr><th>Name</th><th>Age</th></tr>
+///         <tr><td>John</td><td>20</td></tr>
+///     </table>
+///     "#,
+/// );
+/// let table = BorrowedTable::find_first_from_html(&html).unwrap();
+/// let row = table.iter().next().unwrap();
+/// assert_eq!(Some("John"), row.get("Name"));
+/// assert_eq!(Some("20"), row.get("Age"));
+/// ```
+///
+/// [`Html`]: https://docs.rs/scraper/*/scraper/html/struct.Html.html
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BorrowedTable<'a> {
+    headers: HeaderInfo,
+    data: Vec<Vec<Cow<'a, str>>>,
+}
+
+impl<'a> BorrowedTable<'a> {
+    /// Finds the first table in `html`.
+    pub fn find_first_from_html(html: &'a Html) -> Option<BorrowedTable<'a>> {
+        BorrowedTable::find_first_from_elem(&html.root_element())
+    }
+
+    /// Finds the first table within `element`.
+    pub fn find_first_from_elem(
+        element: &ElementRef<'a>,
+    ) -> Option<BorrowedTable<'a>> {
+        element.select(&SEL_TABLE).next().map(BorrowedTable::new)
+    }
+
+    /// Creates a table from a `<table>` element.
+    pub fn new(element: ElementRef<'a>) -> BorrowedTable<'a> {
+        let options = TableBuilder::new().text_only(true).expand_spans(false);
+        let content = |cell: ElementRef| options.content(cell);
+        let sections = options.sections(element, &SEL_CELL, &content);
+        let data = sections
+            .body
+            .into_iter()
+            .map(|(_, tr)| {
+                child_cells(tr, &SEL_CELL).map(borrow_text).collect()
+            })
+            .collect();
+        BorrowedTable {
+            headers: sections.headers,
+            data,
+        }
+    }
+
+    /// Returns the headers, mapping each to its first column. See
+    /// [`Table::headers`](struct.Table.html#method.headers).
+    pub fn headers(&self) -> &Headers {
+        &self.headers.map
+    }
+
+    /// Returns the header names ordered by their column positions.
+    pub fn header_names(&self) -> Vec<&str> {
+        self.headers.names.iter().map(String::as_str).collect()
+    }
+
+    /// Returns the number of rows in the table.
+    pub fn len(&self) -> usize {
+        self.data.len()
+    }
+
+    /// Returns `true` if the table has no rows.
+    pub fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+
+    /// Returns an iterator over the rows of the table.
+    pub fn iter(&self) -> impl Iterator<Item = BorrowedRow<'_, 'a>> + '_ {
+        self.data.iter().map(move |cells| BorrowedRow {
+            headers: &self.headers,
+            cells,
+        })
+    }
+
+    /// Returns the row at the zero-based position `index`, or `None` if the
+    /// table does not have that many rows.
+    pub fn row(&self, index: usize) -> Option<BorrowedRow<'_, 'a>> {
+        self.data.get(index).map(|cells| BorrowedRow {
+            headers: &self.headers,
+            cells,
+        })
+    }
+
+    /// Copies the table into an owned [`Table`](struct.Table.html).
+    pub fn to_table(&self) -> Table {
+        let data: Vec<Vec<String>> = self
+            .data
+            .iter()
+            .map(|row| row.iter().map(|cell| cell.to_string()).collect())
+            .collect();
+        Table {
+            headers: self.headers.clone(),
+            cells: vec![Vec::new(); data.len()],
+            rows: vec![RowInfo::default(); data.len()],
+            data,
+            footer: Vec::new(),
+            caption: None,
+            id: None,
+            classes: Vec::new(),
+        }
+    }
+}
+
+/// A row in a [`BorrowedTable`](struct.BorrowedTable.html).
+///
+/// The cells are borrowed from the table for `'t`, and those that borrow
+/// from the document do so for `'a`, so [`as_slice`](#method.as_slice) can be
+/// used to keep them after the table is dropped.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct BorrowedRow<'t, 'a> {
+    headers: &'t HeaderInfo,
+    cells: &'t [Cow<'a, str>],
+}
+
+impl<'t, 'a> BorrowedRow<'t, 'a> {
+    /// Returns the number of cells in the row.
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    /// Returns `true` if the row contains no cells.
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    /// Returns the cell underneath `header`. See
+    /// [`Row::get`](struct.Row.html#method.get).
+    pub fn get(&self, header: &str) -> Option<&'t str> {
+        self.headers.index(header).and_then(|i| self.get_index(i))
+    }
+
+    /// Returns the cell at the zero-based position `index`.
+    pub fn get_index(&self, index: usize) -> Option<&'t str> {
+        self.cells.get(index).map(|cell| &**cell)
+    }
+
+    /// Returns a slice containing all the cells.
+    pub fn as_slice(&self) -> &'t [Cow<'a, str>] {
+        self.cells
+    }
+}
+
+/// The largest `rowspan` value honored, as in the HTML specification.
+const MAX_ROWSPAN: usize = 65534;
+
+/// The largest `colspan` value honored, as in the HTML specification.
+const MAX_COLSPAN: usize = 1000;
+
+/// The rows of a `<table>` element, sorted into sections, along with their
+/// zero-based positions among all its rows.
+struct Sections<'a> {
+    /// The headers built from the header rows.
+    headers: HeaderInfo,
+    /// The rows outside the header rows and `<tfoot>`.
+    body: Vec<(usize, ElementRef<'a>)>,
+    /// The rows in the `<tfoot>`.
+    foot: Vec<(usize, ElementRef<'a>)>,
+}
+
+/// A position in a row, filled by a cell that may span several rows and
+/// columns.
+#[derive(Clone)]
+struct Slot {
+    /// The content of the cell.
+    content: String,
+    /// Information about the cell.
+    info: Cell,
+    /// The number of rows between the cell's first row and this one.
+    row_offset: usize,
+    /// The number of columns between the cell's first column and this one.
+    col_offset: usize,
+}
+
+impl Slot {
+    fn new(content: String, info: Cell) -> Slot {
+        Slot {
+            content,
+            info,
+            row_offset: 0,
+            col_offset: 0,
+        }
+    }
+
+    /// Splits the slot into its content and information, recording its offsets
+    /// in the information if it was not filled by the cell's first position.
+    fn into_parts(mut self) -> (String, Cell) {
+        if self.row_offset > 0 || self.col_offset > 0 {
+            self.info.span = CellSpan::Spanned {
+                row_offset: self.row_offset,
+                col_offset: self.col_offset,
+            };
+        }
+        (self.content, self.info)
+    }
+}
+
+/// Cells that extend into later rows via `rowspan`, indexed by column.
+///
+/// Each entry holds the slot and the number of rows it still covers.
+struct RowSpans {
+    pending: Vec<Option<(Slot, usize)>>,
+    fill: SpanFill,
+}
+
+impl RowSpans {
+    fn new(fill: SpanFill) -> RowSpans {
+        RowSpans {
+            pending: Vec::new(),
+            fill,
+        }
+    }
+
+    /// Lays out the cells of a row, filling in the columns occupied by cells
+    /// from previous rows.
+    ///
+    /// The contents of each cell are extracted with `content`.
+    fn layout<'a, I>(
+        &mut self,
+        cells: I,
+        options: &TableBuilder,
+        content: &dyn Fn(ElementRef) -> String,
+    ) -> Vec<Slot>
+    where
+        I: Iterator<Item = ElementRef<'a>>,
+    {
+        let mut row = Vec::new();
+        let mut cells = cells.peekable();
+        while cells.peek().is_some() {
+            let col = row.len();
+            if let Some(slot) = self.take(col) {
+                row.push(slot);
+                continue;
+            }
+            let cell = cells.next().unwrap();
+            let origin = Slot::new(content(cell), Cell::new(cell, options));
+            let filler = match self.fill {
+                SpanFill::Duplicate => origin.clone(),
+                SpanFill::Empty => Slot::new(String::new(), Cell::default()),
+            };
+            let (rows, cols) = if options.expand_spans {
+                (rowspan(cell), colspan(cell))
+            } else {
+                (1, 1)
+            };
+            for col_offset in 0..cols {
+                let col = row.len();
+                // A cell from a previous row overlapping this one is dropped.
+                self.take(col);
+                if rows > 1 {
+                    if self.pending.len() <= col {
+                        self.pending.resize(col + 1, None);
+                    }
+                    let slot = Slot {
+                        col_offset,
+                        ..filler.clone()
+                    };
+                    self.pending[col] = Some((slot, rows - 1));
+                }
+                let slot = if col_offset == 0 { &origin } else { &filler };
+                row.push(Slot {
+                    col_offset,
+                    ..slot.clone()
+                });
+            }
+        }
+        let last = self.pending.iter().rposition(Option::is_some);
+        if let Some(last) = last.filter(|&last| last >= row.len()) {
+            for col in row.len()..=last {
+                let slot = self.take(col);
+                row.push(slot.unwrap_or_else(|| {
+                    Slot::new(String::new(), Cell::default())
+                }));
+            }
+        }
+        row
+    }
+
+    /// Returns the slot spanning into column `col` of the current row, if any,
+    /// and marks that row as covered.
+    fn take(&mut self, col: usize) -> Option<Slot> {
+        let entry = self.pending.get_mut(col)?;
+        let (slot, remaining) = entry.as_mut()?;
+        slot.row_offset += 1;
+        *remaining -= 1;
+        if *remaining == 0 {
+            entry.take().map(|(slot, _)| slot)
+        } else {
+            Some(slot.clone())
+        }
+    }
+}
+
+/// Lays out the `<td>` cells of a section of rows, returning their contents and
+/// appending information about them to `cells`.
+fn layout_rows<'a, I>(
+    rows: I,
+    sel_cell: &Selector,
+    options: &TableBuilder,
+    content: &dyn Fn(ElementRef) -> String,
+    cells: &mut Vec<Vec<Cell>>,
+) -> Vec<Vec<String>>
+where
+    I: IntoIterator<Item = ElementRef<'a>>,
+{
+    let mut spans = RowSpans::new(options.span_fill);
+    rows.into_iter()
+        .map(|tr| {
+            let slots =
+                spans.layout(child_cells(tr, sel_cell), options, content);
+            let (contents, info) =
+                slots.into_iter().map(Slot::into_parts).unzip();
+            cells.push(info);
+            contents
+        })
+        .collect()
+}
+
+/// Pads a row with empty cells until it has at least `width` of them, for
+/// [`TableBuilder::pad_rows`](struct.TableBuilder.html#method.pad_rows).
+fn pad_row(contents: &mut Vec<String>, info: &mut Vec<Cell>, width: usize) {
+    if contents.len() < width {
+        contents.resize(width, String::new());
+        info.resize(width, Cell::default());
+    }
+}
+
+/// Builds the headers for a row of header slots. A header spanning several
+/// columns refers to the first one, and a repeated header name refers to its
+/// first occurrence.
+fn header_map(slots: Vec<Slot>) -> HeaderInfo {
+    let mut headers = HeaderInfo {
+        has_row: true,
+        ..HeaderInfo::default()
+    };
+    for (i, slot) in slots.into_iter().enumerate() {
+        let first = slot.col_offset == 0;
+        let (content, info) = slot.into_parts();
+        if first {
+            headers.map.entry(content.clone()).or_insert(i);
+            headers.push(content.clone(), i);
+        }
+        headers.row.push(content);
+        headers.cells.push(info);
+    }
+    headers
+}
+
+/// Prefixes the content of each slot in the header row `last` with the
+/// contents of the header rows `above` it in the same column, joined with
+/// `separator`, skipping empty contents and repeats of the one above.
+fn combine_names(last: &mut [Slot], above: &[Vec<Slot>], separator: &str) {
+    for (i, slot) in last.iter_mut().enumerate() {
+        let mut names: Vec<&str> = Vec::new();
+        let column = above.iter().filter_map(|row| row.get(i));
+        let column = column.map(|above| above.content.as_str());
+        for name in column.chain(Some(slot.content.as_str())) {
+            if !name.is_empty() && names.last() != Some(&name) {
+                names.push(name);
+            }
+        }
+        slot.content = names.join(separator);
+    }
+}
+
+/// Builds headers from the `<col>` elements in the `<colgroup>`s of `table`,
+/// naming each column after its `<col>`'s id or class.
+fn col_headers(table: ElementRef) -> HeaderInfo {
+    let mut headers = HeaderInfo::default();
+    let mut col = 0;
+    for colgroup in child_elements(table, "colgroup") {
+        let mut cols = child_elements(colgroup, "col").peekable();
+        if cols.peek().is_none() {
+            col += span(colgroup, "span");
+        }
+        for element in cols {
+            let attr = |name| element.value().attr(name).map(str::trim);
+            let name = attr("id").filter(|s| !s.is_empty()).or(attr("class"));
+            if let Some(name) = name.filter(|s| !s.is_empty()) {
+                headers.map.entry(name.to_string()).or_insert(col);
+                headers.push(name.to_string(), col);
+            }
+            col += span(element, "span");
+        }
+    }
+    headers.row = vec![String::new(); col];
+    for (name, &i) in headers.names.iter().zip(&headers.columns) {
+        headers.row[i] = name.clone();
+    }
+    headers.cells = vec![Cell::default(); col];
+    headers
+}
+
+/// Returns true if a row outside a `<thead>` looks like a header row, meaning
+/// it has at least one `<th>` cell and all of its `<td>` cells are blank.
+///
+/// Blank `<td>` cells are allowed for the corner above a column of row
+/// headers, but a row like `<th>John</th><td>20</td>` is a row of data with a
+/// row header.
+fn is_header_row(tr: ElementRef) -> bool {
+    let mut has_th = false;
+    for cell in child_cells(tr, &SEL_CELL) {
+        if cell.value().name() == "th" {
+            has_th = true;
+        } else if cell.text().any(|text| !text.trim().is_empty()) {
+            return false;
+        }
+    }
+    has_th
+}
+
+/// Returns true if `element` has a `hidden` attribute or an inline style of
+/// `display: none`.
+fn is_hidden(element: ElementRef) -> bool {
+    let value = element.value();
+    if value.attr("hidden").is_some() {
+        return true;
+    }
+    let style = value.attr("style").unwrap_or("");
+    style.split(';').any(|declaration| {
+        let mut parts = declaration.splitn(2, ':');
+        let name = parts.next().unwrap_or("").trim();
+        let value = parts.next().unwrap_or("").trim();
+        name.eq_ignore_ascii_case("display")
+            && value
+                .split_ascii_whitespace()
+                .next()
+                .is_some_and(|v| v.eq_ignore_ascii_case("none"))
+    })
+}
+
+/// Returns the text of a cell without copying it when it comes from a single
+/// text node, trimmed like the contents of a [`Table`](struct.Table.html).
+fn borrow_text(cell: ElementRef) -> Cow<str> {
+    let mut texts = cell
+        .text()
+        .map(|text| trim_cell(text, TextMode::Text))
+        .filter(|text| !text.is_empty());
+    match (texts.next(), texts.next()) {
+        (None, _) => Cow::Borrowed(""),
+        (Some(text), None) => Cow::Borrowed(text),
+        _ => {
+            let text = cell.text().collect::<String>();
+            Cow::Owned(trim_cell(&text, TextMode::Text).to_string())
+        }
+    }
+}
+
+/// Returns the classes of `element` in the order they appear in its `class`
+/// attribute. Unlike `Element::classes`, this does not sort them.
+fn class_list(element: ElementRef) -> Vec<String> {
+    element
+        .value()
+        .attr("class")
+        .map_or_else(Vec::new, |class| {
+            class.split_whitespace().map(String::from).collect()
+        })
+}
+
+/// Returns the children of `element` with the tag name `name`.
+fn child_elements<'a>(
+    element: ElementRef<'a>,
+    name: &'a str,
+) -> impl Iterator<Item = ElementRef<'a>> {
+    element
+        .children()
+        .filter_map(ElementRef::wrap)
+        .filter(move |child| child.value().name() == name)
+}
+
+/// Returns the cells of a row that match `selector`. Unlike `select`, this
+/// excludes cells of tables nested within the row.
+fn child_cells<'a, 'b>(
+    tr: ElementRef<'a>,
+    selector: &'b Selector,
+) -> impl Iterator<Item = ElementRef<'a>> + 'b
+where
+    'a: 'b,
+{
+    tr.children()
+        .filter_map(ElementRef::wrap)
+        .filter(move |cell| selector.matches(cell))
+}
+
+/// Returns true if `element` belongs to a table nested within `root`, rather
+/// than to `root` itself. If `root` is not a `<table>`, elements of the tables
+/// directly within it are not considered nested.
+fn is_nested(element: ElementRef, root: ElementRef) -> bool {
+    let mut depth = 0;
+    for ancestor in element.ancestors().filter_map(ElementRef::wrap) {
+        if ancestor == root {
+            break;
+        }
+        if ancestor.value().name() == "table" {
+            depth += 1;
+        }
+    }
+    let allowed = if root.value().name() == "table" { 0 } else { 1 };
+    depth > allowed
+}
+
+/// Returns true if `element` is a child of an element named `name`.
+fn parent_is(element: ElementRef, name: &str) -> bool {
+    element
+        .parent()
+        .and_then(ElementRef::wrap)
+        .is_some_and(|parent| parent.value().name() == name)
+}
+
+/// Returns the number of rows spanned by a cell according to its `rowspan`
+/// attribute. Following the HTML specification, zero means the cell spans all
+/// remaining rows, and invalid values are treated as 1.
+fn rowspan(element: ElementRef) -> usize {
+    let value = element.value().attr("rowspan");
+    match value.and_then(|s| s.trim().parse::<usize>().ok()) {
+        Some(0) => usize::MAX,
+        Some(n) => n.min(MAX_ROWSPAN),
+        None => 1,
+    }
+}
+
+/// Returns the number of columns spanned by a cell according to its `colspan`
+/// attribute. Zero and invalid values are treated as 1.
+fn colspan(element: ElementRef) -> usize {
+    span(element, "colspan")
+}
+
+/// Returns the number of columns spanned by an element according to the
+/// attribute `attr`, which is `span` for `<col>` and `<colgroup>` elements.
+/// Zero and invalid values are treated as 1.
+fn span(element: ElementRef, attr: &str) -> usize {
+    let value = element.value().attr(attr);
+    match value.and_then(|s| s.trim().parse::<usize>().ok()) {
+        Some(0) | None => 1,
+        Some(n) => n.min(MAX_COLSPAN),
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'a> serde::Serialize for Row<'a> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.collect_seq(self.cells)
+    }
+}
+
+// Fixed selectors, parsed once and shared by every table.
+static SEL_TABLE: Lazy<Selector> = Lazy::new(|| css("table"));
+static SEL_TR: Lazy<Selector> = Lazy::new(|| css("tr"));
+static SEL_TH: Lazy<Selector> = Lazy::new(|| css("th"));
+static SEL_CELL: Lazy<Selector> = Lazy::new(|| css("th, td"));
+static SEL_LINK: Lazy<Selector> = Lazy::new(|| css("a[href]"));
+
+/// Parses a fixed selector that is known to be valid. Selectors given by users
+/// must be parsed with `Selector::parse` instead, so that errors are reported.
+fn css(selector: &'static str) -> Selector {
+    Selector::parse(selector).unwrap()
+}
+
+fn select_cells(tr: ElementRef, selector: &Selector) -> Vec<String> {
+    let options = TableBuilder::default();
+    child_cells(tr, selector)
+        .map(|e| options.content(e))
+        .collect()
+}
+
+/// Parses a number for [`Row::parse_number`](struct.Row.html#method.parse_number),
+/// removing currency symbols, thousands separators, and whitespace.
+fn parse_number(s: &str) -> Option<f64> {
+    let s = s.trim();
+    let (negative, s) = match s.strip_prefix('(') {
+        Some(inner) if inner.ends_with(')') => {
+            (true, &inner[..inner.len() - 1])
+        }
+        _ => (false, s),
+    };
+    let number: String = s
+        .chars()
+        .filter(|&c| !matches!(c, '$' | '€' | '£' | '¥' | ','))
+        .filter(|c| !c.is_whitespace())
+        .collect();
+    if negative && number.starts_with(['-', '+']) {
+        return None;
+    }
+    let value: f64 = number.parse().ok().filter(|v: &f64| v.is_finite())?;
+    Some(if negative { -value } else { value })
+}
+
+/// Trims and lowercases a header name for
+/// [`Row::get_normalized`](struct.Row.html#method.get_normalized).
+fn normalize_header(header: &str) -> String {
+    header.trim().to_lowercase()
+}
+
+/// Normalizes a header name for
+/// [`TableBuilder::normalize_headers`](struct.TableBuilder.html#method.normalize_headers),
+/// collapsing whitespace and stripping whitespace and the characters in
+/// `trailing` from its ends.
+fn normalize_key(header: &str, trailing: &str) -> String {
+    let header = collapse_whitespace(header);
+    let header = header.trim_start();
+    let header = header
+        .trim_end_matches(|c: char| c.is_whitespace() || trailing.contains(c));
+    header.to_string()
+}
+
+/// Folds a header name for
+/// [`TableBuilder::ascii_fold`](struct.TableBuilder.html#method.ascii_fold),
+/// lowercasing it and removing common diacritics.
+fn fold_header(header: &str) -> String {
+    let mut result = String::with_capacity(header.len());
+    for c in header.chars().flat_map(char::to_lowercase) {
+        match c {
+            '\u{300}'..='\u{36F}' => {}
+            'à' | 'á' | 'â' | 'ã' | 'ä' | 'å' | 'ā' | 'ă' | 'ą' => {
+                result.push('a')
+            }
+            'æ' => result.push_str("ae"),
+            'ç' | 'ć' | 'ĉ' | 'ċ' | 'č' => result.push('c'),
+            'ð' | 'ď' | 'đ' => result.push('d'),
+            'è' | 'é' | 'ê' | 'ë' | 'ē' | 'ĕ' | 'ė' | 'ę' | 'ě' => {
+                result.push('e')
+            }
+            'ĝ' | 'ğ' | 'ġ' | 'ģ' => result.push('g'),
+            'ĥ' | 'ħ' => result.push('h'),
+            'ì' | 'í' | 'î' | 'ï' | 'ĩ' | 'ī' | 'ĭ' | 'į' | 'ı' => {
+                result.push('i')
+            }
+            'ĳ' => result.push_str("ij"),
+            'ĵ' => result.push('j'),
+            'ķ' | 'ĸ' => result.push('k'),
+            'ĺ' | 'ļ' | 'ľ' | 'ŀ' | 'ł' => result.push('l'),
+            'ñ' | 'ń' | 'ņ' | 'ň' | 'ŉ' | 'ŋ' => result.push('n'),
+            'ò' | 'ó' | 'ô' | 'õ' | 'ö' | 'ø' | 'ō' | 'ŏ' | 'ő' => {
+                result.push('o')
+            }
+            'œ' => result.push_str("oe"),
+            'ŕ' | 'ŗ' | 'ř' => result.push('r'),
+            'ś' | 'ŝ' | 'ş' | 'š' | 'ſ' => result.push('s'),
+            'ß' => result.push_str("ss"),
+            'ţ' | 'ť' | 'ŧ' => result.push('t'),
+            'þ' => result.push_str("th"),
+            'ù' | 'ú' | 'û' | 'ü' | 'ũ' | 'ū' | 'ŭ' | 'ů' | 'ű' | 'ų' => {
+                result.push('u')
+            }
+            'ŵ' => result.push('w'),
+            'ý' | 'ÿ' | 'ŷ' => result.push('y'),
+            'ź' | 'ż' | 'ž' => result.push('z'),
+            _ => result.push(c),
+        }
+    }
+    result
+}
+
+/// Escapes the characters in `s` that have special meaning in HTML.
+fn escape_html(s: &str) -> String {
+    let mut result = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '&' => result.push_str("&amp;"),
+            '<' => result.push_str("&lt;"),
+            '>' => result.push_str("&gt;"),
+            '"' => result.push_str("&quot;"),
+            _ => result.push(c),
+        }
+    }
+    result
+}
+
+/// Appends a row of [`Table::to_ascii`](struct.Table.html#method.to_ascii)
+/// to `ascii`, padding each cell to its width in `widths` and treating cells
+/// past the end of `row` as empty.
+fn push_ascii_row(ascii: &mut String, row: &[&str], widths: &[usize]) {
+    let lines: Vec<Vec<_>> = row
+        .iter()
+        .map(|content| content.lines().collect())
+        .collect();
+    let height = lines.iter().map(Vec::len).max().unwrap_or(0).max(1);
+    for line in 0..height {
+        ascii.push('|');
+        for (i, width) in widths.iter().enumerate() {
+            let text = lines.get(i).and_then(|l| l.get(line)).unwrap_or(&"");
+            ascii.push(' ');
+            ascii.push_str(text);
+            let padding = width - text.chars().count();
+            ascii.push_str(&" ".repeat(padding + 1));
+            ascii.push('|');
+        }
+        ascii.push('\n');
+    }
+}
+
+/// Elements that are rendered on their own lines, for
+/// [`TableBuilder::line_breaks`](struct.TableBuilder.html#method.line_breaks).
+const BLOCK_ELEMENTS: &[&str] = &[
+    "address",
+    "article",
+    "aside",
+    "blockquote",
+    "dd",
+    "div",
+    "dl",
+    "dt",
+    "figcaption",
+    "figure",
+    "footer",
+    "h1",
+    "h2",
+    "h3",
+    "h4",
+    "h5",
+    "h6",
+    "header",
+    "hr",
+    "li",
+    "main",
+    "nav",
+    "ol",
+    "p",
+    "pre",
+    "section",
+    "table",
+    "tr",
+    "ul",
+];
+
+/// Splits the text of `element` into lines at `<br>` tags and around
+/// block-level elements.
+fn text_lines(element: ElementRef) -> Vec<String> {
+    let mut lines = vec![String::new()];
+    push_lines(element, &mut lines);
+    lines
+}
+
+fn push_lines(element: ElementRef, lines: &mut Vec<String>) {
+    for child in element.children() {
+        if let Some(text) = child.value().as_text() {
+            lines.last_mut().unwrap().push_str(text);
+        } else if let Some(child) = ElementRef::wrap(child) {
+            let name = child.value().name();
+            if name == "br" {
+                lines.push(String::new());
+                continue;
+            }
+            let block = BLOCK_ELEMENTS.contains(&name);
+            if block {
+                break_line(lines);
+            }
+            push_lines(child, lines);
+            if block {
+                break_line(lines);
+            }
+        }
+    }
+}
+
+/// Starts a new line, unless the current one is still blank.
+fn break_line(lines: &mut Vec<String>) {
+    if !lines.last().unwrap().trim().is_empty() {
+        lines.push(String::new());
+    }
+}
+
+/// Removes whitespace and invisible characters from the start and end of a
+/// cell's contents, including `&nbsp;` entities in HTML.
+fn trim_cell(content: &str, mode: TextMode) -> &str {
+    let mut content = content;
+    loop {
+        let len = content.len();
+        content = content.trim_matches(|c: char| {
+            c.is_whitespace()
+                || matches!(
+                    c,
+                    '\u{200B}'
+                        ..='\u{200D}' | '\u{2060}' | '\u{FEFF}' | '\u{AD}'
+                )
+        });
+        if mode == TextMode::Html {
+            content = content.trim_start_matches("&nbsp;");
+            content = content.trim_end_matches("&nbsp;");
+        }
+        if content.len() == len {
+            return content;
+        }
+    }
+}
+
+/// Replaces each run of ASCII whitespace in `s` with a single space, as
+/// browsers do when rendering inline text.
+fn collapse_whitespace(s: &str) -> String {
+    let mut result = String::with_capacity(s.len());
+    let mut in_whitespace = false;
+    for c in s.chars() {
+        if c.is_ascii_whitespace() {
+            if !in_whitespace {
+                result.push(' ');
+            }
+            in_whitespace = true;
+        } else {
+            result.push(c);
+            in_whitespace = false;
+        }
+    }
+    result
+}
+
+/// Selects the table with an id of `id` under `element`.
+fn select_by_id<'a>(
+    element: &ElementRef<'a>,
+    id: &str,
+) -> Result<ElementRef<'a>, TableError> {
+    let selector = format!("table#{}", escape_css_ident(id));
+    let selector = Selector::parse(&selector)
+        .map_err(|_| TableError::InvalidSelector(selector.clone()))?;
+    let table = element.select(&selector).next();
+    table.ok_or(TableError::NoTableFound)
+}
+
+/// Escapes `ident` for use as a CSS identifier, following the `CSS.escape`
+/// algorithm, so that it matches literally in a selector.
+fn escape_css_ident(ident: &str) -> String {
+    let mut result = String::with_capacity(ident.len());
+    let starts_with_dash = ident.starts_with('-');
+    for (i, c) in ident.chars().enumerate() {
+        match c {
+            '\0' => result.push('\u{FFFD}'),
+            '\x01'..='\x1f' | '\x7f' => {
+                result.push_str(&format!("\\{:x} ", c as u32))
+            }
+            '0'..='9' if i == 0 || (i == 1 && starts_with_dash) => {
+                result.push_str(&format!("\\{:x} ", c as u32))
+            }
+            '-' if i == 0 && ident.len() == 1 => result.push_str("\\-"),
+            '-' | '_' | 'a'..='z' | 'A'..='Z' | '0'..='9' => result.push(c),
+            _ if !c.is_ascii() => result.push(c),
+            _ => {
+                result.push('\\');
+                result.push(c);
+            }
+        }
+    }
+    result
+}
+
+/// Selects the first table under `element` with a class of `class`.
+fn select_by_class<'a>(
+    element: &ElementRef<'a>,
+    class: &str,
+) -> Option<ElementRef<'a>> {
+    element
+        .select(&SEL_TABLE)
+        .find(|table| table.value().classes().any(|c| c == class))
+}
+
+/// Selects the first table under `element` with a cell whose text, trimmed
+/// and with its whitespace collapsed, satisfies `matches`.
+fn select_by_cell<'a, F>(
+    element: &ElementRef<'a>,
+    matches: F,
+) -> Option<ElementRef<'a>>
+where
+    F: Fn(&str) -> bool,
+{
+    element.select(&SEL_TABLE).find(|&table| {
+        table
+            .select(&SEL_TR)
+            .filter(|&tr| !is_nested(tr, table))
+            .flat_map(|tr| child_cells(tr, &SEL_CELL))
+            .any(|cell| {
+                let text =
+                    collapse_whitespace(&cell.text().collect::<String>());
+                matches(trim_cell(&text, TextMode::Text))
+            })
+    })
+}
+
+/// Selects the first table under `element` whose first row contains all of
+/// `headers`, or the first table if `headers` is empty.
+fn select_by_headers<'a, T>(
+    element: &ElementRef<'a>,
+    headers: &[T],
+) -> Option<(usize, ElementRef<'a>)>
+where
+    T: AsRef<str>,
+{
+    if headers.is_empty() {
+        return element.select(&SEL_TABLE).next().map(|table| (0, table));
+    }
+    select_by_header_cells(element, |cells| {
+        headers.iter().all(|h| contains_str(cells, h.as_ref()))
+    })
+}
+
+/// Selects the first table under `element` for which `matches` returns true
+/// when given the `<th>` cells in its first row, along with its position among
+/// all the tables under `element`.
+fn select_by_header_cells<'a, F>(
+    element: &ElementRef<'a>,
+    matches: F,
+) -> Option<(usize, ElementRef<'a>)>
+where
+    F: Fn(&[String]) -> bool,
+{
+    element.select(&SEL_TABLE).enumerate().find(|&(_, table)| {
+        table
+            .select(&SEL_TR)
+            .find(|&tr| !is_nested(tr, table))
+            .is_some_and(|tr| matches(&select_cells(tr, &SEL_TH)))
+    })
+}
+
+fn contains_str(slice: &[String], item: &str) -> bool {
+    slice.iter().any(|s| s == item)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TABLE_EMPTY: &str = r#"
+<table></table>
+"#;
+
+    const TABLE_TH: &str = r#"
+<table>
+    <tr><th>Name</th><th>Age</th></tr>
+</table>
+"#;
+
+    const TABLE_TD: &str = r#"
+<table>
+    <tr><td>Name</td><td>Age</td></tr>
+</table>
+"#;
+
+    const TABLE_TH_TD: &str = r#"
+<table>
+    <tr><th>Name</th><th>Age</th></tr>
+    <tr><td>John</td><td>20</td></tr>
+</table>
+"#;
+
+    const TABLE_TD_TD: &str = r#"
+<table>
+    <tr><td>Name</td><td>Age</td></tr>
+    <tr><td>John</td><td>20</td></tr>
+</table>
+"#;
+
+    const TABLE_TH_TH: &str = r#"
+<table>
+    <tr><th>Name</th><th>Age</th></tr>
+    <tr><th>John</th><th>20</th></tr>
+</table>
+"#;
+
+    const TABLE_ROW_HEADERS: &str = r#"
+<table>
+    <tr><td></td><th>Speed</th><th>Price</th></tr>
+    <tr><th>Car</th><td>Fast</td><td>High</td></tr>
+    <tr><th>Bike</th><td>Slow</td><td>Low</td></tr>
+    <tr><td>Walk</td><td>Slower</td><td>Free</td></tr>
+</table>
+"#;
+
+    const TABLE_COMPLEX: &str = r#"
+<table>
+    <tr><th>Name</th><th>Age</th><th>Extra</th></tr>
+    <tr><td>John</td><td>20</td></tr>
+    <tr><td>May</td><td>30</td><td>foo</td></tr>
+    <tr></tr>
+    <tr><td>a</td><td>b</td><td>c</td><td>d</td></tr>
+</table>
+"#;
+
+    const TABLE_EMPTY_ROWS: &str = r#"
+<table>
+    <tr><th>Name</th><th>Age</th></tr>
+    <tr></tr>
+    <tr><td>John</td><td>20</td></tr>
+    <tr><td></td><td> </td></tr>
+    <tr><td></td><td>30</td></tr>
+    <tfoot><tr><td></td><td></td></tr></tfoot>
+</table>
+"#;
+
+    const TABLE_COLS: &str = r#"
+<table>
+    <colgroup><col id="name"><col span="2" class="score"></colgroup>
+    <colgroup span="2"></colgroup>
+    <colgroup><col><col class=" notes "></colgroup>
+    <tr><td>John</td><td>1</td><td>2</td><td>a</td><td>b</td><td>c</td><td>d</td></tr>
+</table>
+"#;
+
+    const TABLE_COLS_TH: &str = r#"
+<table>
+    <colgroup><col id="name"><col id="age"></colgroup>
+    <tr><th>Name</th><th>Age</th></tr>
+    <tr><td>John</td><td>20</td></tr>
+</table>
+"#;
+
+    const TABLE_LINE_BREAKS: &str = r#"
+<table>
+    <tr><th>Address</th><th>Notes</th></tr>
+    <tr>
+        <td>1 Main St<br>Springfield</td>
+        <td>
+            <p>First   note</p>
+            <div>Second <i>note</i></div>
+        </td>
+    </tr>
+</table>
+"#;
+
+    const TABLE_HIDDEN_ROWS: &str = r#"
+<table>
+    <tr><th>Name</th><th>Age</th></tr>
+    <tr><td>John</td><td>20</td></tr>
+    <tr hidden><td>May</td><td>30</td></tr>
+    <tr style="color: red; DISPLAY : None !important"><td>Ann</td><td>40</td></tr>
+    <tr style="display: block"><td>Bob</td><td>50</td></tr>
+</table>
+"#;
+
+    const TABLE_HEADER_IN_TBODY: &str = r#"
+<table>
+    <thead><tr><td>John</td><td>20</td></tr></thead>
+    <tbody>
+        <tr><th>Name</th><th>Age</th></tr>
+        <tr><td>May</td><td>30</td></tr>
+    </tbody>
+</table>
+"#;
+
+    const TABLE_MIXED_FIRST_ROW: &str = r#"
+<table>
+    <tr><th>John</th><td>20</td></tr>
+    <tr><th>May</th><td>30</td></tr>
+</table>
+"#;
+
+    const TABLE_ROWSPAN: &str = r#"
+<table>
+    <tr><th>Category</th><th>Item</th><th>Amount</th></tr>
+    <tr><td rowspan="2">Revenue</td><td>Sales</td><td>100</td></tr>
+    <tr><td>Services</td><td>50</td></tr>
+    <tr><td rowspan="3">Expenses</td><td>Rent</td><td>30</td></tr>
+    <tr><td>Wages</td><td>40</td></tr>
+    <tr><td>Taxes</td><td>10</td></tr>
+</table>
+"#;
+
+    const TABLE_ROWSPAN_OVERLAP: &str = r#"
+<table>
+    <tr><td rowspan="3">a</td><td>b</td><td rowspan="2">c</td></tr>
+    <tr><td rowspan="2">d</td></tr>
+    <tr><td>e</td></tr>
+    <tr><td>f</td><td>g</td><td>h</td></tr>
+</table>
+"#;
+
+    const TABLE_ROWSPAN_OVERFLOW: &str = r#"
+<table>
+    <tr><td>a</td><td rowspan="100">b</td></tr>
+    <tr><td>c</td></tr>
+    <tr></tr>
+    <tr><td rowspan="0">d</td><td rowspan="x">e</td></tr>
+</table>
+"#;
+
+    const TABLE_COLSPAN: &str = r#"
+<table>
+    <tr><th colspan="2">Name</th><th>Age</th><th>Weight</th></tr>
+    <tr><td>John</td><td>Smith</td><td>20</td><td>150</td></tr>
+    <tr><td colspan="3">Total</td><td>150</td></tr>
+    <tr><td colspan="0">a</td><td colspan="x">b</td><td>c</td></tr>
+    <tr><td colspan="2" rowspan="2">d</td><td>e</td></tr>
+    <tr><td>f</td></tr>
+</table>
+"#;
+
+    const TABLE_MARKUP: &str = r#"
+<table>
+    <tr><th>Name</th><th><i>Notes</i></th></tr>
+    <tr><td>John <b>Smith</b></td><td><b>a</b><b>b</b> &amp; c</td></tr>
+</table>
+"#;
+
+    const TABLE_THEAD: &str = r#"
+<table>
+    <thead>
+        <tr><th rowspan="2">Name</th><th colspan="2">Address</th></tr>
+        <tr><th>City</th><td>Zip</td></tr>
+    </thead>
+    <tbody>
+        <tr><td>John</td><td>Boston</td><td>02108</td></tr>
+        <tr><td>May</td><td>Denver</td><td>80202</td></tr>
+    </tbody>
+</table>
+"#;
+
+    const TABLE_THEAD_EMPTY: &str = r#"
+<table>
+    <thead></thead>
+    <tr><th>Name</th><th>Age</th></tr>
+    <tr><td>John</td><td>20</td></tr>
+</table>
+"#;
+
+    const TABLE_TFOOT: &str = r#"
+<table>
+    <thead><tr><th>Item</th><th>Amount</th></tr></thead>
+    <tfoot><tr><td>Total</td><td>30</td></tr></tfoot>
+    <tbody>
+        <tr><td>Rent</td><td>20</td></tr>
+        <tr><td>Food</td><td>10</td></tr>
+    </tbody>
+</table>
+"#;
+
+    const TABLE_CAPTION: &str = r#"
+<table>
+    <caption>
+        Ages &amp; <i>names</i>
+    </caption>
+    <tr><th>Name</th><th>Age</th></tr>
+    <tr><td>John</td><td>20</td></tr>
+</table>
+"#;
+
+    const TABLE_LINKS: &str = r#"
+<table>
+    <tr><th>Name</th><th>Links</th></tr>
+    <tr><td><a href="/person/42">John</a></td><td>none</td></tr>
+    <tr>
+        <td><a>May</a></td>
+        <td><a href="/a">a</a>, <a href="/b">b</a></td>
+    </tr>
+</table>
+"#;
+
+    const TABLE_WHITESPACE: &str = "
+<table>
+    <tr><th>Name</th><th>Notes</th></tr>
+    <tr><td>John\n   Smith</td><td>\ta \t\t b\r\n\n c\u{a0} d </td></tr>
+</table>
+";
+
+    const TABLE_DUPLICATE_HEADERS: &str = r#"
+<table>
+    <tr><th>Value</th><th>Name</th><th>Value</th><th>Value</th></tr>
+    <tr><td>1</td><td>John</td><td>2</td></tr>
+</table>
+"#;
+
+    const TABLE_NESTED: &str = r#"
+<table id="outer">
+    <tr><th>Name</th><th>Details</th></tr>
+    <tr>
+        <td>John</td>
+        <td>
+            <table id="inner">
+                <tr><th>Key</th><th>Value</th></tr>
+                <tr><td>Age</td><td>20</td></tr>
+                <tr><td>Weight</td><td>150</td></tr>
+            </table>
+        </td>
+    </tr>
+    <tr><td>May</td><td>none</td></tr>
+</table>
+"#;
+
+    const TABLE_NESTED_HEADERS: &str = r#"
+<table id="outer">
+    <tr>
+        <td>
+            <table id="inner">
+                <tr><th>Name</th><th>Age</th></tr>
+                <tr><td>John</td><td>20</td></tr>
+            </table>
+        </td>
+        <td>Notes</td>
+    </tr>
+</table>
+"#;
+
+    const HTML_TEMPLATE: &str = r#"
+<!doctype HTML>
+<html>
+    <body>
+        <template id="rows">
+            <table>
+                <tr><th>Name</th><th>Age</th></tr>
+                <tr><td>John</td><td>20</td></tr>
+            </table>
+        </template>
+    </body>
+</html>
+"#;
+
+    const HTML_LAYOUT: &str = r#"
+<!doctype HTML>
+<html>
+    <body>
+        <table><tr><td>Home</td></tr></table>
+        <table>
+            <tr><td>Menu</td></tr>
+            <tr><td>Search</td></tr>
+        </table>
+        <table>
+            <tr><td>John</td><td>20</td></tr>
+            <tr><td>May</td><td>30</td></tr>
+        </table>
+    </body>
+</html>
+"#;
+
+    const TABLE_TITLE_ROW: &str = r#"
+<table>
+    <tr><th colspan="2">People</th></tr>
+    <tr><td colspan="2"></td></tr>
+    <tr><th>Name</th><th>Age</th></tr>
+    <tr><td>John</td><td>20</td></tr>
+    <tfoot><tr><th>Name</th><th>Age</th></tr></tfoot>
+</table>
+"#;
+
+    const HTML_COMPLEX_JUNK_WITH_TABLES: &str = r#"
+<!doctype HTML>
+<html>
+    <body>
+        <div class="header">
+            <table><tr><td>Logo</td><td>Search</td></tr></table>
+        </div>
+        <div class="section"><p>No tables here.</p></div>
+        <div class="section" id="main_page">
+            <p>Some text.</p>
+            <table class="data">
+                <tr><th>Name</th><th>Age</th></tr>
+                <tr><td>John</td><td>20</td></tr>
+            </table>
+            <table class="data">
+                <tr><th>Name</th><th>Age</th></tr>
+                <tr><td>May</td><td>30</td></tr>
+            </table>
+        </div>
+        <table><tr><td>Footer</td></tr></table>
+    </body>
+</html>
+"#;
+
+    const HTML_NO_TABLE: &str = r#"
+<!doctype HTML>
+<html>
+    <head><title>foo</title></head>
+    <body><p>Hi.</p></body>
+</html>
+"#;
+
+    const HTML_TWO_TABLES: &str = r#"
+<!doctype HTML>
+<html>
+    <head><title>foo</title></head>
+    <body>
+        <table id="first">
+            <tr><th>Name</th><th>Age</th></tr>
+            <tr><td>John</td><td>20</td></tr>
+        </table>
+        <table id="second">
+            <tr><th>Name</th><th>Weight</th></tr>
+            <tr><td>John</td><td>150</td></tr>
+        </table>
+    </body>
+</html>
+"#;
+
+    const HTML_SPECIAL_IDS: &str = r#"
+<table id="a:b"><tr><td>colon</td></tr></table>
+<table id="a.b"><tr><td>dot</td></tr></table>
+<table id="my table"><tr><td>space</td></tr></table>
+<table id="1st"><tr><td>digit</td></tr></table>
+<table id="-"><tr><td>dash</td></tr></table>
+"#;
+
+    const HTML_CLASSES: &str = r#"
+<table class="layout"><tr><td>Hello</td></tr></table>
+<table class="data report">
+    <tr><th>Name</th><th>Age</th></tr>
+    <tr><td>John</td><td>20</td></tr>
+</table>
+<table class="data other">
+    <tr><th>Name</th><th>Weight</th></tr>
+    <tr><td>John</td><td>150</td></tr>
+</table>
+"#;
+
+    const HTML_DATA_ROLE: &str = r#"
+<table data-role="layout"><tr><td>Hello</td></tr></table>
+<table data-role="results">
+    <tr><th>Name</th><th>Age</th></tr>
+    <tr><td>John</td><td>20</td></tr>
+</table>
+"#;
+
+    const HTML_TABLE_FRAGMENT: &str = r#"
+        <table id="first">
+            <tr><th>Name</th><th>Age</th></tr>
+            <tr><td>John</td><td>20</td></tr>
+        </table>
+    </body>
+</html>
+"#;
+
+    #[test]
+    fn test_find_first_none() {
+        assert_eq!(None, Table::find_first(""));
+        assert_eq!(None, Table::find_first("foo"));
+        assert_eq!(None, Table::find_first(HTML_NO_TABLE));
+    }
+
+    #[test]
+    fn test_find_first_empty() {
+        let empty = Table {
+            headers: HeaderInfo::default(),
+            data: Vec::new(),
+            footer: Vec::new(),
+            caption: None,
+            cells: Vec::new(),
+            rows: Vec::new(),
+            id: None,
+            classes: Vec::new(),
+        };
+        assert_eq!(Some(empty), Table::find_first(TABLE_EMPTY));
+    }
+
+    #[test]
+    fn test_find_first_some() {
+        assert!(Table::find_first(TABLE_TH).is_some());
+        assert!(Table::find_first(TABLE_TD).is_some());
+    }
+
+    #[test]
+    fn test_find_all() {
+        assert!(Table::find_all("").is_empty());
+        assert!(Table::find_all(HTML_NO_TABLE).is_empty());
+        assert_eq!(1, Table::find_all(TABLE_TH_TD).len());
+
+        let tables = Table::find_all(HTML_TWO_TABLES);
+        assert_eq!(2, tables.len());
+        assert!(tables[0].headers().contains_key("Age"));
+        assert!(tables[1].headers().contains_key("Weight"));
+    }
+
+    #[test]
+    fn test_find_from_elem() {
+        let html = Html::parse_document(HTML_TWO_TABLES);
+        let body = html.select(&css("body")).next().unwrap();
+        assert_eq!(2, Table::find_all_from_elem(&body).len());
+        assert_eq!(
+            Table::find_first(HTML_TWO_TABLES),
+            Table::find_first_from_elem(&body)
+        );
+        assert_eq!(
+            Table::find_by_id(HTML_TWO_TABLES, "second"),
+            Table::find_by_id_from_elem(&body, "second")
+        );
+        assert_eq!(
+            Table::find_by_headers(HTML_TWO_TABLES, &["Weight"]),
+            Table::find_by_headers_from_html(&html, &["Weight"])
+        );
+    }
+
+    #[test]
+    fn test_try_find_first() {
+        let error = Err(TableError::NoTableFound);
+        assert_eq!(error, Table::try_find_first(""));
+        assert_eq!(error, Table::try_find_first(HTML_NO_TABLE));
+        assert_eq!(
+            Table::find_first(TABLE_TH_TD),
+            Table::try_find_first(TABLE_TH_TD).ok()
+        );
+    }
+
+    #[test]
+    fn test_try_find_by_id() {
+        let error = Err(TableError::NoTableFound);
+        assert_eq!(error, Table::try_find_by_id(HTML_NO_TABLE, "id"));
+        assert_eq!(error, Table::try_find_by_id(HTML_TWO_TABLES, "third"));
+        assert_eq!(
+            Err(TableError::InvalidSelector("table#".to_string())),
+            Table::try_find_by_id(HTML_TWO_TABLES, "")
+        );
+        assert_eq!(
+            Table::find_by_id(HTML_TWO_TABLES, "first"),
+            Table::try_find_by_id(HTML_TWO_TABLES, "first").ok()
+        );
+    }
+
+    #[test]
+    fn test_find_by_id_none() {
+        assert_eq!(None, Table::find_by_id("", ""));
+        assert_eq!(None, Table::find_by_id("foo", "id"));
+        assert_eq!(None, Table::find_by_id(HTML_NO_TABLE, "id"));
+
+        assert_eq!(None, Table::find_by_id(TABLE_EMPTY, "id"));
+        assert_eq!(None, Table::find_by_id(TABLE_TH, "id"));
+        assert_eq!(None, Table::find_by_id(TABLE_TH, ""));
+        assert_eq!(None, Table::find_by_id(HTML_TWO_TABLES, "id"));
+    }
+
+    #[test]
+    fn test_find_by_id_some() {
+        assert!(Table::find_by_id(HTML_TWO_TABLES, "first").is_some());
+        assert!(Table::find_by_id(HTML_TWO_TABLES, "second").is_some());
+    }
+
+    #[test]
+    fn test_find_by_class_none() {
+        assert_eq!(None, Table::find_by_class("", ""));
+        assert_eq!(None, Table::find_by_class(HTML_NO_TABLE, "data"));
+        assert_eq!(None, Table::find_by_class(TABLE_TH, "data"));
+        assert_eq!(None, Table::find_by_class(HTML_CLASSES, "dat"));
+        assert_eq!(None, Table::find_by_class(HTML_CLASSES, ""));
+        assert_eq!(None, Table::find_by_class(HTML_CLASSES, "data report"));
+        assert_eq!(None, Table::find_by_class(HTML_CLASSES, "data td"));
+        assert_eq!(None, Table::find_by_class(HTML_CLASSES, "#!["));
+    }
+
+    #[test]
+    fn test_find_by_class_some() {
+        let table = Table::find_by_class(HTML_CLASSES, "data").unwrap();
+        assert!(table.headers().contains_key("Age"));
+        let table = Table::find_by_class(HTML_CLASSES, "report").unwrap();
+        assert!(table.headers().contains_key("Age"));
+        let table = Table::find_by_class(HTML_CLASSES, "other").unwrap();
+        assert!(table.headers().contains_key("Weight"));
+    }
+
+    #[test]
+    fn test_find_by_selector() {
+        let selector = r#"table[data-role="results"]"#;
+        assert_eq!(None, Table::find_by_selector("", selector));
+        assert_eq!(None, Table::find_by_selector(HTML_NO_TABLE, selector));
+        assert_eq!(None, Table::find_by_selector(HTML_DATA_ROLE, "table["));
+        assert_eq!(None, Table::find_by_selector(HTML_DATA_ROLE, ""));
+
+        let table = Table::find_by_selector(HTML_DATA_ROLE, selector).unwrap();
+        assert!(table.headers().contains_key("Age"));
+        assert_eq!(
+            Table::find_first(HTML_DATA_ROLE),
+            Table::find_by_selector(HTML_DATA_ROLE, "table")
+        );
+
+        let html = Html::parse_fragment(HTML_DATA_ROLE);
+        let selector = Selector::parse(selector).unwrap();
+        assert_eq!(
+            Some(table),
+            Table::find_by_selector_from_html(&html, &selector)
+        );
+    }
+
+    #[test]
+    fn test_find_by_headers_empty() {
+        let headers: [&str; 0] = [];
+
+        assert_eq!(None, Table::find_by_headers("", &headers));
+        assert_eq!(None, Table::find_by_headers("foo", &headers));
+        assert_eq!(None, Table::find_by_headers(HTML_NO_TABLE, &headers));
+
+        assert!(Table::find_by_headers(TABLE_EMPTY, &headers).is_some());
+        assert!(Table::find_by_headers(HTML_TWO_TABLES, &headers).is_some());
+    }
+
+    #[test]
+    fn test_find_by_headers_none() {
+        let headers = ["Name", "Age"];
+        let bad_headers = ["Name", "BAD"];
+
+        assert_eq!(None, Table::find_by_headers("", &headers));
+        assert_eq!(None, Table::find_by_headers("foo", &headers));
+        assert_eq!(None, Table::find_by_headers(HTML_NO_TABLE, &headers));
+
+        assert_eq!(None, Table::find_by_headers(TABLE_EMPTY, &bad_headers));
+        assert_eq!(None, Table::find_by_headers(TABLE_TH, &bad_headers));
+
+        assert_eq!(None, Table::find_by_headers(TABLE_TD, &headers));
+        assert_eq!(None, Table::find_by_headers(TABLE_TD, &bad_headers));
+    }
+
+    #[test]
+    fn test_find_by_headers_some() {
+        let headers: [&str; 0] = [];
+        assert!(Table::find_by_headers(TABLE_TH, &headers).is_some());
+        assert!(Table::find_by_headers(TABLE_TH_TD, &headers).is_some());
+        assert!(Table::find_by_headers(HTML_TWO_TABLES, &headers).is_some());
+
+        let headers = ["Name"];
+        assert!(Table::find_by_headers(TABLE_TH, &headers).is_some());
+        assert!(Table::find_by_headers(TABLE_TH_TD, &headers).is_some());
+        assert!(Table::find_by_headers(HTML_TWO_TABLES, &headers).is_some());
+
+        let headers = ["Age", "Name"];
+        assert!(Table::find_by_headers(TABLE_TH, &headers).is_some());
+        assert!(Table::find_by_headers(TABLE_TH_TD, &headers).is_some());
+        assert!(Table::find_by_headers(HTML_TWO_TABLES, &headers).is_some());
+    }
+
+    #[test]
+    fn test_find_by_headers_ci() {
+        assert_eq!(None, Table::find_by_headers(TABLE_TH, &["name", "AGE"]));
+        assert_eq!(
+            Table::find_by_headers(TABLE_TH, &["Name", "Age"]),
+            Table::find_by_headers_ci(TABLE_TH, &["name", "AGE"])
+        );
+        assert_eq!(None, Table::find_by_headers_ci(TABLE_TD, &["name"]));
+        assert_eq!(None, Table::find_by_headers_ci(TABLE_TH, &["nam"]));
+
+        let table = Table::find_by_headers_ci(HTML_TWO_TABLES, &["WEIGHT"]);
+        assert_eq!(Table::find_by_id(HTML_TWO_TABLES, "second"), table);
+    }
+
+    #[test]
+    fn test_find_first_incomplete_fragment() {
+        assert!(Table::find_first(HTML_TABLE_FRAGMENT).is_some());
+    }
+
+    #[test]
+    fn test_headers_empty() {
+        let empty = HashMap::new();
+        assert_eq!(&empty, Table::find_first(TABLE_TD).unwrap().headers());
+        assert_eq!(&empty, Table::find_first(TABLE_TD_TD).unwrap().headers());
+    }
+
+    #[test]
+    fn test_headers_nonempty() {
+        let mut headers = HashMap::new();
+        headers.insert("Name".to_string(), 0);
+        headers.insert("Age".to_string(), 1);
+
+        assert_eq!(&headers, Table::find_first(TABLE_TH).unwrap().headers());
+        assert_eq!(&headers, Table::find_first(TABLE_TH_TD).unwrap().headers());
+        assert_eq!(&headers, Table::find_first(TABLE_TH_TH).unwrap().headers());
+
+        headers.insert("Extra".to_string(), 2);
+        assert_eq!(
+            &headers,
+            Table::find_first(TABLE_COMPLEX).unwrap().headers()
+        );
+    }
+
+    #[test]
+    fn test_header_names() {
+        let empty: Vec<&str> = Vec::new();
+        assert_eq!(
+            empty,
+            Table::find_first(TABLE_EMPTY).unwrap().header_names()
+        );
+        assert_eq!(empty, Table::find_first(TABLE_TD).unwrap().header_names());
+
+        let table = Table::find_first(TABLE_TH_TD).unwrap();
+        assert_eq!(vec!["Name", "Age"], table.header_names());
+
+        let table = Table::find_first(TABLE_COMPLEX).unwrap();
+        assert_eq!(vec!["Name", "Age", "Extra"], table.header_names());
+
+        let table = Table::find_first(TABLE_COLSPAN).unwrap();
+        assert_eq!(vec!["Name", "Age", "Weight"], table.header_names());
+    }
+
+    #[test]
+    fn test_iter_empty() {
+        assert_eq!(0, Table::find_first(TABLE_EMPTY).unwrap().iter().count());
+        assert_eq!(0, Table::find_first(TABLE_TH).unwrap().iter().count());
+    }
+
+    #[test]
+    fn test_iter_nonempty() {
+        assert_eq!(1, Table::find_first(TABLE_TD).unwrap().iter().count());
+        assert_eq!(1, Table::find_first(TABLE_TH_TD).unwrap().iter().count());
+        assert_eq!(2, Table::find_first(TABLE_TD_TD).unwrap().iter().count());
+        assert_eq!(1, Table::find_first(TABLE_TH_TH).unwrap().iter().count());
+        assert_eq!(4, Table::find_first(TABLE_COMPLEX).unwrap().iter().count());
+    }
+
+    #[test]
+    fn test_column() {
+        let table = Table::find_first(TABLE_TD).unwrap();
+        assert_eq!(None, table.column("Name"));
+
+        let table = Table::find_first(TABLE_COMPLEX).unwrap();
+        assert_eq!(None, table.column("foo"));
+        assert_eq!(Some(vec!["John", "May", "a"]), table.column("Name"));
+        assert_eq!(Some(vec!["foo", "c"]), table.column("Extra"));
+    }
+
+    #[test]
+    fn test_column_index() {
+        let table = Table::find_first(TABLE_TD_TD).unwrap();
+        assert_eq!(vec!["Name", "John"], table.column_index(0));
+        assert_eq!(vec!["Age", "20"], table.column_index(1));
+        assert!(table.column_index(2).is_empty());
+
+        let table = Table::find_first(TABLE_COMPLEX).unwrap();
+        assert_eq!(vec!["d"], table.column_index(3));
+    }
+
+    #[test]
+    fn test_is_empty() {
+        assert!(Table::find_first(TABLE_EMPTY).unwrap().is_empty());
+        assert!(Table::find_first(TABLE_TH).unwrap().is_empty());
+        assert!(!Table::find_first(TABLE_TD).unwrap().is_empty());
+        assert!(!Table::find_first(TABLE_TH_TD).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_len() {
+        for html in &[TABLE_EMPTY, TABLE_TH, TABLE_TD_TD, TABLE_COMPLEX] {
+            let table = Table::find_first(html).unwrap();
+            assert_eq!(table.iter().count(), table.len());
+        }
+    }
+
+    #[test]
+    fn test_row() {
+        assert_eq!(None, Table::find_first(TABLE_EMPTY).unwrap().row(0));
+        assert_eq!(None, Table::find_first(TABLE_TH).unwrap().row(0));
+
+        let table = Table::find_first(TABLE_COMPLEX).unwrap();
+        for (i, row) in table.iter().enumerate() {
+            assert_eq!(Some(row), table.row(i));
+        }
+        assert_eq!(Some("May"), table.row(1).unwrap().get("Name"));
+        assert_eq!(None, table.row(4));
+        assert_eq!(None, table.row(usize::MAX));
+    }
+
+    #[test]
+    fn test_row_is_empty() {
+        let table = Table::find_first(TABLE_TD).unwrap();
+        assert_eq!(
+            vec![false],
+            table.iter().map(|r| r.is_empty()).collect::<Vec<_>>()
+        );
+
+        let table = Table::find_first(TABLE_COMPLEX).unwrap();
+        assert_eq!(
+            vec![false, false, true, false],
+            table.iter().map(|r| r.is_empty()).collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_row_len() {
+        let table = Table::find_first(TABLE_TD).unwrap();
+        assert_eq!(vec![2], table.iter().map(|r| r.len()).collect::<Vec<_>>());
+
+        let table = Table::find_first(TABLE_COMPLEX).unwrap();
+        assert_eq!(
+            vec![2, 3, 0, 4],
+            table.iter().map(|r| r.len()).collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_row_get_without_headers() {
+        let table = Table::find_first(TABLE_TD).unwrap();
+        let mut iter = table.iter();
+        let row = iter.next().unwrap();
+
+        assert_eq!(None, row.get(""));
+        assert_eq!(None, row.get("foo"));
+        assert_eq!(None, row.get("Name"));
+        assert_eq!(None, row.get("Age"));
+
+        assert_eq!(None, iter.next());
+    }
+
+    #[test]
+    fn test_row_get_with_headers() {
+        let table = Table::find_first(TABLE_TH_TD).unwrap();
+        let mut iter = table.iter();
+        let row = iter.next().unwrap();
+
+        assert_eq!(None, row.get(""));
+        assert_eq!(None, row.get("foo"));
+        assert_eq!(Some("John"), row.get("Name"));
+        assert_eq!(Some("20"), row.get("Age"));
+
+        assert_eq!(None, iter.next());
+    }
+
+    #[test]
+    fn test_row_get_complex() {
+        let table = Table::find_first(TABLE_COMPLEX).unwrap();
+        let mut iter = table.iter();
+
+        let row = iter.next().unwrap();
+        assert_eq!(Some("John"), row.get("Name"));
+        assert_eq!(Some("20"), row.get("Age"));
+        assert_eq!(None, row.get("Extra"));
+
+        let row = iter.next().unwrap();
+        assert_eq!(Some("May"), row.get("Name"));
+        assert_eq!(Some("30"), row.get("Age"));
+        assert_eq!(Some("foo"), row.get("Extra"));
+
+        let row = iter.next().unwrap();
+        assert_eq!(None, row.get("Name"));
+        assert_eq!(None, row.get("Age"));
+        assert_eq!(None, row.get("Extra"));
+
+        let row = iter.next().unwrap();
+        assert_eq!(Some("a"), row.get("Name"));
+        assert_eq!(Some("b"), row.get("Age"));
+        assert_eq!(Some("c"), row.get("Extra"));
+
+        assert_eq!(None, iter.next());
+    }
+
+    #[test]
+    fn test_row_get_ci() {
+        let table = Table::find_first(TABLE_TH_TD).unwrap();
+        let row = table.iter().next().unwrap();
+        assert_eq!(None, row.get("NAME"));
+        assert_eq!(Some("John"), row.get_ci("NAME"));
+        assert_eq!(Some("20"), row.get_ci("age"));
+        assert_eq!(None, row.get_ci("foo"));
+
+        let table = Table::find_first(TABLE_TD).unwrap();
+        assert_eq!(None, table.iter().next().unwrap().get_ci("name"));
+    }
+
+    #[test]
+    fn test_row_parse() {
+        let table = Table::find_first(TABLE_COMPLEX).unwrap();
+        let mut iter = table.iter();
+
+        let row = iter.next().unwrap();
+        assert_eq!(Some(Ok(20)), row.parse::<u32>("Age"));
+        assert_eq!(Some(Ok(20.0)), row.parse::<f64>("Age"));
+        assert!(row.parse::<u32>("Name").unwrap().is_err());
+        assert_eq!(None, row.parse::<u32>("Extra"));
+        assert_eq!(None, row.parse::<u32>("foo"));
+
+        let row = iter.next().unwrap();
+        assert_eq!(Some(Ok("foo".to_string())), row.parse::<String>("Extra"));
+    }
+
+    #[test]
+    fn test_row_to_map() {
+        let table = Table::find_first(TABLE_TD).unwrap();
+        assert!(table.iter().next().unwrap().to_map().is_empty());
+
+        let table = Table::find_first(TABLE_COMPLEX).unwrap();
+        let mut iter = table.iter();
+
+        let mut map = HashMap::new();
+        map.insert("Name", "John");
+        map.insert("Age", "20");
+        assert_eq!(map, iter.next().unwrap().to_map());
+
+        map.insert("Name", "May");
+        map.insert("Age", "30");
+        map.insert("Extra", "foo");
+        assert_eq!(map, iter.next().unwrap().to_map());
+
+        assert!(iter.next().unwrap().to_map().is_empty());
+        assert_eq!(3, iter.next().unwrap().to_map().len());
+    }
+
+    #[test]
+    fn test_row_get_index() {
+        let table = Table::find_first(TABLE_TD).unwrap();
+        let row = table.iter().next().unwrap();
+        assert_eq!(Some("Name"), row.get_index(0));
+        assert_eq!(Some("Age"), row.get_index(1));
+        assert_eq!(None, row.get_index(2));
+        assert_eq!(None, row.get_index(usize::MAX));
+
+        let table = Table::find_first(TABLE_COMPLEX).unwrap();
+        let mut iter = table.iter();
+        assert_eq!(None, iter.next().unwrap().get_index(2));
+        assert_eq!(Some("foo"), iter.next().unwrap().get_index(2));
+        assert_eq!(None, iter.next().unwrap().get_index(0));
+    }
+
+    #[test]
+    fn test_row_as_slice_without_headers() {
+        let table = Table::find_first(TABLE_TD).unwrap();
+        let mut iter = table.iter();
+
+        assert_eq!(&["Name", "Age"], iter.next().unwrap().as_slice());
+        assert_eq!(None, iter.next());
+    }
+
+    #[test]
+    fn test_row_as_slice_with_headers() {
+        let table = Table::find_first(TABLE_TH_TD).unwrap();
+        let mut iter = table.iter();
+
+        assert_eq!(&["John", "20"], iter.next().unwrap().as_slice());
+        assert_eq!(None, iter.next());
+    }
+
+    #[test]
+    fn test_row_as_slice_complex() {
+        let table = Table::find_first(TABLE_COMPLEX).unwrap();
+        let mut iter = table.iter();
+        let empty: [&str; 0] = [];
+
+        assert_eq!(&["John", "20"], iter.next().unwrap().as_slice());
+        assert_eq!(&["May", "30", "foo"], iter.next().unwrap().as_slice());
+        assert_eq!(&empty, iter.next().unwrap().as_slice());
+        assert_eq!(&["a", "b", "c", "d"], iter.next().unwrap().as_slice());
+        assert_eq!(None, iter.next());
+    }
+
+    #[test]
+    fn test_row_iter_simple() {
+        let table = Table::find_first(TABLE_TD).unwrap();
+        let row = table.iter().next().unwrap();
+        let mut iter = row.iter();
+
+        assert_eq!(Some("Name"), iter.next().map(String::as_str));
+        assert_eq!(Some("Age"), iter.next().map(String::as_str));
+        assert_eq!(None, iter.next());
+    }
+
+    #[test]
+    fn test_row_iter_complex() {
+        let table = Table::find_first(TABLE_COMPLEX).unwrap();
+        let mut table_iter = table.iter();
+
+        let row = table_iter.next().unwrap();
+        let mut iter = row.iter();
+        assert_eq!(Some("John"), iter.next().map(String::as_str));
+        assert_eq!(Some("20"), iter.next().map(String::as_str));
+        assert_eq!(None, iter.next());
+
+        let row = table_iter.next().unwrap();
+        let mut iter = row.iter();
+        assert_eq!(Some("May"), iter.next().map(String::as_str));
+        assert_eq!(Some("30"), iter.next().map(String::as_str));
+        assert_eq!(Some("foo"), iter.next().map(String::as_str));
+        assert_eq!(None, iter.next());
+
+        let row = table_iter.next().unwrap();
+        let mut iter = row.iter();
+        assert_eq!(None, iter.next());
+
+        let row = table_iter.next().unwrap();
+        let mut iter = row.iter();
+        assert_eq!(Some("a"), iter.next().map(String::as_str));
+        assert_eq!(Some("b"), iter.next().map(String::as_str));
+        assert_eq!(Some("c"), iter.next().map(String::as_str));
+        assert_eq!(Some("d"), iter.next().map(String::as_str));
+        assert_eq!(None, iter.next());
+    }
+
+    #[test]
+    fn test_rowspan() {
+        let table = Table::find_first(TABLE_ROWSPAN).unwrap();
+        let rows: Vec<_> = table.iter().map(|r| r.as_slice()).collect();
+        assert_eq!(
+            vec![
+                ["Revenue", "Sales", "100"],
+                ["Revenue", "Services", "50"],
+                ["Expenses", "Rent", "30"],
+                ["Expenses", "Wages", "40"],
+                ["Expenses", "Taxes", "10"],
+            ],
+            rows
+        );
+        for row in &table {
+            assert!(row.get("Amount").is_some());
+        }
+    }
+
+    #[test]
+    fn test_rowspan_overlap() {
+        let table = Table::find_first(TABLE_ROWSPAN_OVERLAP).unwrap();
+        let rows: Vec<_> = table.iter().map(|r| r.as_slice()).collect();
+        assert_eq!(
+            vec![
+                ["a", "b", "c"],
+                ["a", "d", "c"],
+                ["a", "d", "e"],
+                ["f", "g", "h"],
+            ],
+            rows
+        );
+    }
+
+    #[test]
+    fn test_rowspan_overflow() {
+        let table = Table::find_first(TABLE_ROWSPAN_OVERFLOW).unwrap();
+        let mut iter = table.iter();
+        assert_eq!(&["a", "b"], iter.next().unwrap().as_slice());
+        assert_eq!(&["c", "b"], iter.next().unwrap().as_slice());
+        assert_eq!(&["", "b"], iter.next().unwrap().as_slice());
+        assert_eq!(&["d", "b", "e"], iter.next().unwrap().as_slice());
+        assert_eq!(None, iter.next());
+    }
+
+    #[test]
+    fn test_colspan() {
+        let table = Table::find_first(TABLE_COLSPAN).unwrap();
+        let mut headers = HashMap::new();
+        headers.insert("Name".to_string(), 0);
+        headers.insert("Age".to_string(), 2);
+        headers.insert("Weight".to_string(), 3);
+        assert_eq!(&headers, table.headers());
+
+        let mut iter = table.iter();
+        let row = iter.next().unwrap();
+        assert_eq!(Some("John"), row.get("Name"));
+        assert_eq!(Some("20"), row.get("Age"));
+        assert_eq!(Some("150"), row.get("Weight"));
+
+        let row = iter.next().unwrap();
+        assert_eq!(&["Total", "Total", "Total", "150"], row.as_slice());
+        assert_eq!(Some("150"), row.get("Weight"));
+
+        assert_eq!(&["a", "b", "c"], iter.next().unwrap().as_slice());
+        assert_eq!(&["d", "d", "e"], iter.next().unwrap().as_slice());
+        assert_eq!(&["d", "d", "f"], iter.next().unwrap().as_slice());
+        assert_eq!(None, iter.next());
+    }
+
+    #[test]
+    fn test_text_mode_html() {
+        let html = Html::parse_fragment(TABLE_MARKUP);
+        let element = html.select(&css("table")).next().unwrap();
+        let table = Table::new_with_mode(element, TextMode::Html);
+        assert_eq!(table, Table::find_first(TABLE_MARKUP).unwrap());
+
+        let row = table.iter().next().unwrap();
+        assert_eq!(Some("John <b>Smith</b>"), row.get("Name"));
+        assert_eq!(Some("<b>a</b><b>b</b> &amp; c"), row.get("<i>Notes</i>"));
+    }
+
+    #[test]
+    fn test_text_mode_text() {
+        let html = Html::parse_fragment(TABLE_MARKUP);
+        let element = html.select(&css("table")).next().unwrap();
+        let table = Table::new_with_mode(element, TextMode::Text);
+
+        let row = table.iter().next().unwrap();
+        assert_eq!(Some("John Smith"), row.get("Name"));
+        assert_eq!(Some("ab & c"), row.get("Notes"));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde_table_round_trip() {
+        for html in &[TABLE_EMPTY, TABLE_TH_TD, TABLE_TD_TD, TABLE_COMPLEX] {
+            let table = Table::find_first(html).unwrap();
+            let json = serde_json::to_string(&table).unwrap();
+            assert_eq!(table, serde_json::from_str(&json).unwrap());
+        }
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde_row() {
+        let table = Table::find_first(TABLE_TH_TD).unwrap();
+        let row = table.iter().next().unwrap();
+        assert_eq!(r#"["John","20"]"#, serde_json::to_string(&row).unwrap());
+    }
+
+    #[test]
+    fn test_thead_multiple_rows() {
+        let table = Table::find_first(TABLE_THEAD).unwrap();
+        let mut headers = HashMap::new();
+        headers.insert("Name".to_string(), 0);
+        headers.insert("City".to_string(), 1);
+        headers.insert("Zip".to_string(), 2);
+        assert_eq!(&headers, table.headers());
+
+        let mut iter = table.iter();
+        let row = iter.next().unwrap();
+        assert_eq!(Some("John"), row.get("Name"));
+        assert_eq!(Some("Boston"), row.get("City"));
+        assert_eq!(Some("02108"), row.get("Zip"));
+        assert_eq!(Some("May"), iter.next().unwrap().get("Name"));
+        assert_eq!(None, iter.next());
+    }
+
+    #[test]
+    fn test_thead_empty() {
+        assert_eq!(
+            Table::find_first(TABLE_TH_TD),
+            Table::find_first(TABLE_THEAD_EMPTY)
+        );
+    }
+
+    #[test]
+    fn test_footer_empty() {
+        assert_eq!(0, Table::find_first(TABLE_EMPTY).unwrap().footer().count());
+        assert_eq!(
+            0,
+            Table::find_first(TABLE_COMPLEX).unwrap().footer().count()
+        );
+    }
+
+    #[test]
+    fn test_footer_nonempty() {
+        let table = Table::find_first(TABLE_TFOOT).unwrap();
+        let items: Vec<_> = table.iter().map(|r| r.get("Item")).collect();
+        assert_eq!(vec![Some("Rent"), Some("Food")], items);
+
+        let mut footer = table.footer();
+        let row = footer.next().unwrap();
+        assert_eq!(Some("Total"), row.get("Item"));
+        assert_eq!(Some("30"), row.get("Amount"));
+        assert_eq!(None, footer.next());
+    }
+
+    #[test]
+    fn test_footer_inconsistent() {
+        // A deserialized table may have fewer cells and rows than it claims.
+        let mut table = Table::find_first(TABLE_TFOOT).unwrap();
+        table.cells.truncate(1);
+        table.rows.clear();
+        let footer: Vec<_> = table.footer().map(|r| r.as_slice()).collect();
+        assert_eq!(vec![&["Total", "30"]], footer);
+
+        let concat = Table::concat(&[table.clone(), table.clone()]).unwrap();
+        assert_eq!(4, concat.len());
+        assert_eq!(2, concat.footer().count());
+        assert_eq!(6, concat.cells.len());
+        assert_eq!(6, concat.rows.len());
+        let transposed = table.transpose();
+        assert_eq!(&["Item", "Rent", "Food"], transposed.data()[0].as_slice());
+
+        table.data.push(vec![String::new()]);
+        let table = table.without_empty_rows();
+        assert_eq!(&[vec!["Rent", "20"], vec!["Food", "10"]], table.data());
+        assert_eq!(1, table.footer().count());
+    }
+
+    #[test]
+    fn test_caption_none() {
+        assert_eq!(None, Table::find_first(TABLE_EMPTY).unwrap().caption());
+        assert_eq!(None, Table::find_first(TABLE_TH_TD).unwrap().caption());
+    }
+
+    #[test]
+    fn test_caption_some() {
+        let table = Table::find_first(TABLE_CAPTION).unwrap();
+        assert_eq!(Some("Ages &amp; <i>names</i>"), table.caption());
+        assert_eq!(1, table.iter().count());
+
+        let html = Html::parse_fragment(TABLE_CAPTION);
+        let element = html.select(&css("table")).next().unwrap();
+        let table = Table::new_with_mode(element, TextMode::Text);
+        assert_eq!(Some("Ages & names"), table.caption());
+    }
+
+    #[test]
+    fn test_row_get_links() {
+        let table = Table::find_first(TABLE_LINKS).unwrap();
+        let mut iter = table.iter();
+        let empty: Vec<&str> = Vec::new();
+
+        let row = iter.next().unwrap();
+        assert_eq!(vec!["/person/42"], row.get_links("Name"));
+        assert_eq!(empty, row.get_links("Links"));
+        assert_eq!(empty, row.get_links("foo"));
+
+        let row = iter.next().unwrap();
+        assert_eq!(empty, row.get_links("Name"));
+        assert_eq!(vec!["/a", "/b"], row.get_links("Links"));
+    }
+
+    #[test]
+    fn test_whitespace_preserve() {
+        let table = Table::find_first(TABLE_WHITESPACE).unwrap();
+        let row = table.iter().next().unwrap();
+        assert_eq!(Some("John\n   Smith"), row.get("Name"));
+        assert_eq!(Some("a \t\t b\n\n c&nbsp; d"), row.get("Notes"));
+    }
+
+    #[test]
+    fn test_whitespace_collapse() {
+        let html = Html::parse_fragment(TABLE_WHITESPACE);
+        let element = html.select(&css("table")).next().unwrap();
+        let table = TableBuilder::new()
+            .whitespace(Whitespace::Collapse)
+            .build(element);
+        let row = table.iter().next().unwrap();
+        assert_eq!(Some("John Smith"), row.get("Name"));
+        assert_eq!(Some("a b c&nbsp; d"), row.get("Notes"));
+    }
+
+    #[test]
+    fn test_builder_default() {
+        let builder = TableBuilder::new();
+        for html in &[TABLE_EMPTY, TABLE_TH_TD, TABLE_COMPLEX, TABLE_COLSPAN] {
+            assert_eq!(Table::find_first(html), builder.find_first(html));
+        }
+        assert_eq!(
+            Table::find_all(HTML_TWO_TABLES),
+            builder.find_all(HTML_TWO_TABLES)
+        );
+        assert_eq!(
+            Table::find_by_id(HTML_TWO_TABLES, "second"),
+            builder.find_by_id(HTML_TWO_TABLES, "second")
+        );
+        assert_eq!(
+            Table::find_by_class(HTML_CLASSES, "other"),
+            builder.find_by_class(HTML_CLASSES, "other")
+        );
+        assert_eq!(
+            Table::find_by_selector(HTML_DATA_ROLE, "[data-role=results]"),
+            builder.find_by_selector(HTML_DATA_ROLE, "[data-role=results]")
+        );
+        assert_eq!(
+            Table::find_by_headers(HTML_TWO_TABLES, &["Weight"]),
+            builder.find_by_headers(HTML_TWO_TABLES, &["Weight"])
+        );
+    }
+
+    #[test]
+    fn test_builder_options() {
+        let table = TableBuilder::new()
+            .text_only(true)
+            .collapse_whitespace(true)
+            .find_first(TABLE_WHITESPACE)
+            .unwrap();
+        assert_eq!(Some("a b c\u{a0} d"), table.row(0).unwrap().get("Notes"));
+
+        let table = TableBuilder::new()
+            .text_only(true)
+            .text_only(false)
+            .find_first(TABLE_MARKUP)
+            .unwrap();
+        assert_eq!(Table::find_first(TABLE_MARKUP), Some(table));
+    }
+
+    #[test]
+    fn test_builder_span_fill() {
+        let table = TableBuilder::new()
+            .span_fill(SpanFill::Empty)
+            .find_first(TABLE_COLSPAN)
+            .unwrap();
+        assert_eq!(Some(&2), table.headers().get("Age"));
+        let mut iter = table.iter();
+        assert_eq!(Some("150"), iter.next().unwrap().get("Weight"));
+        assert_eq!(&["Total", "", "", "150"], iter.next().unwrap().as_slice());
+        assert_eq!(&["a", "b", "c"], iter.next().unwrap().as_slice());
+        assert_eq!(&["d", "", "e"], iter.next().unwrap().as_slice());
+        assert_eq!(&["", "", "f"], iter.next().unwrap().as_slice());
+    }
+
+    #[test]
+    fn test_builder_expand_spans() {
+        let table = TableBuilder::new()
+            .expand_spans(false)
+            .find_first(TABLE_COLSPAN)
+            .unwrap();
+        assert_eq!(Some(&1), table.headers().get("Age"));
+        let mut iter = table.iter();
+        assert_eq!(
+            &["John", "Smith", "20", "150"],
+            iter.next().unwrap().as_slice()
+        );
+        assert_eq!(&["Total", "150"], iter.next().unwrap().as_slice());
+        iter.next();
+        assert_eq!(&["d", "e"], iter.next().unwrap().as_slice());
+        assert_eq!(&["f"], iter.next().unwrap().as_slice());
+    }
+
+    #[test]
+    fn test_duplicate_headers() {
+        let table = Table::find_first(TABLE_DUPLICATE_HEADERS).unwrap();
+        assert_eq!(Some(&0), table.headers().get("Value"));
+        assert_eq!(
+            vec!["Value", "Name", "Value", "Value"],
+            table.header_names()
+        );
+
+        let row = table.iter().next().unwrap();
+        assert_eq!(Some("1"), row.get("Value"));
+        assert_eq!(vec!["1", "2"], row.get_all("Value"));
+        assert_eq!(vec!["John"], row.get_all("Name"));
+        assert!(row.get_all("foo").is_empty());
+    }
+
+    #[test]
+    fn test_nested_tables() {
+        let table = Table::find_first(TABLE_NESTED).unwrap();
+        assert_eq!(vec!["Name", "Details"], table.header_names());
+        assert_eq!(2, table.iter().count());
+        assert_eq!(vec!["John", "May"], table.column("Name").unwrap());
+        assert!(table
+            .row(0)
+            .unwrap()
+            .get("Details")
+            .unwrap()
+            .contains("<table"));
+        assert_eq!(Some("none"), table.row(1).unwrap().get("Details"));
+
+        let inner = Table::find_by_id(TABLE_NESTED, "inner").unwrap();
+        assert_eq!(vec!["Key", "Value"], inner.header_names());
+        assert_eq!(vec!["Age", "Weight"], inner.column("Key").unwrap());
+    }
+
+    #[test]
+    fn test_nested_tables_in_selected_element() {
+        let html = format!("<div>{}</div>", TABLE_NESTED);
+        let table = Table::find_first(TABLE_NESTED).unwrap();
+        let div = Table::find_by_selector(&html, "div").unwrap();
+        assert_eq!(table.header_names(), div.header_names());
+        assert_eq!(table.data(), div.data());
+    }
+
+    #[test]
+    fn test_find_by_headers_nested() {
+        assert_eq!(
+            Table::find_by_id(TABLE_NESTED_HEADERS, "inner"),
+            Table::find_by_headers(TABLE_NESTED_HEADERS, &["Name", "Age"])
+        );
+        assert_eq!(
+            Table::find_by_id(TABLE_NESTED, "outer"),
+            Table::find_by_headers(TABLE_NESTED, &["Name"])
+        );
+        assert_eq!(
+            Table::find_by_id(TABLE_NESTED, "inner"),
+            Table::find_by_headers(TABLE_NESTED, &["Key"])
+        );
+        assert_eq!(
+            None,
+            Table::find_by_headers(TABLE_NESTED, &["Name", "Key"])
+        );
+    }
+
+    #[test]
+    fn test_into_iter_owned() {
+        let table = Table::find_first(TABLE_COMPLEX).unwrap();
+        let expected: Vec<_> =
+            table.iter().map(|r| r.as_slice().to_vec()).collect();
+        let names: Vec<_> = table.iter().map(|r| r.get("Name")).collect();
+        let names: Vec<_> =
+            names.into_iter().map(|n| n.map(String::from)).collect();
+
+        let rows: Vec<OwnedRow> = table.into_iter().collect();
+        let handle = std::thread::spawn(move || {
+            let owned_names: Vec<_> = rows
+                .iter()
+                .map(|r| r.get("Name").map(String::from))
+                .collect();
+            assert_eq!(names, owned_names);
+            rows.into_iter().map(OwnedRow::into_vec).collect::<Vec<_>>()
+        });
+        assert_eq!(expected, handle.join().unwrap());
+    }
+
+    #[test]
+    fn test_into_iter_excludes_footer() {
+        let table = Table::find_first(TABLE_TFOOT).unwrap();
+        let row = table.into_iter().last().unwrap();
+        assert_eq!(Some("Food"), row.as_row().get("Item"));
+        assert_eq!(&["Food", "10"], row.as_slice());
+    }
+
+    #[test]
+    fn test_row_get_html_and_text() {
+        let table = Table::find_first(TABLE_MARKUP).unwrap();
+        let row = table.row(0).unwrap();
+        assert_eq!(Some("John <b>Smith</b>"), row.get("Name"));
+        assert_eq!(Some("John <b>Smith</b>"), row.get_html("Name"));
+        assert_eq!(Some("John Smith"), row.get_text("Name"));
+        assert_eq!(None, row.get_html("foo"));
+        assert_eq!(None, row.get_text("foo"));
+
+        let table = TableBuilder::new()
+            .text_only(true)
+            .find_first(TABLE_MARKUP)
+            .unwrap();
+        let row = table.row(0).unwrap();
+        assert_eq!(Some("ab & c"), row.get("Notes"));
+        assert_eq!(Some("<b>a</b><b>b</b> &amp; c"), row.get_html("Notes"));
+        assert_eq!(Some("ab & c"), row.get_text("Notes"));
+    }
+
+    #[test]
+    fn test_row_cell() {
+        let table = Table::find_first(TABLE_LINKS).unwrap();
+        let row = table.row(0).unwrap();
+        let cell = row.cell("Name").unwrap();
+        assert_eq!(r#"<a href="/person/42">John</a>"#, cell.html());
+        assert_eq!("John", cell.text());
+        assert_eq!(vec!["/person/42"], cell.links());
+        assert_eq!(Some(cell), row.cell_index(0));
+        assert_eq!(None, row.cell("foo"));
+        assert_eq!(None, row.cell_index(2));
+    }
+
+    #[test]
+    fn test_row_header() {
+        let table = Table::find_first(TABLE_ROW_HEADERS).unwrap();
+        let mut iter = table.iter();
+
+        let row = iter.next().unwrap();
+        assert_eq!(&["Car", "Fast", "High"], row.as_slice());
+        assert_eq!(Some("Car"), row.row_header());
+        assert_eq!(Some("Fast"), row.get("Speed"));
+        assert!(row.cell_index(0).unwrap().is_header());
+        assert!(!row.cell_index(1).unwrap().is_header());
+
+        let row = iter.next().unwrap();
+        assert_eq!(Some("Bike"), row.row_header());
+        assert_eq!(Some("Low"), row.get("Price"));
+
+        let row = iter.next().unwrap();
+        assert_eq!(None, row.row_header());
+        assert_eq!(Some("Free"), row.get("Price"));
+
+        assert!(iter.next().is_none());
+    }
+
+    #[test]
+    fn test_th_in_body_row() {
+        let table = Table::find_first(TABLE_TH_TH).unwrap();
+        let row = table.iter().next().unwrap();
+        assert_eq!(&["John", "20"], row.as_slice());
+        assert_eq!(Some("20"), row.get("Age"));
+        assert_eq!(Some("John"), row.row_header());
+    }
+
+    #[test]
+    fn test_find_first_from_reader() {
+        let table = Table::find_first_from_reader(TABLE_TD.as_bytes());
+        let table = table.unwrap().unwrap();
+        assert_eq!(Table::find_first(TABLE_TD).unwrap(), table);
+
+        let none = Table::find_first_from_reader(HTML_NO_TABLE.as_bytes());
+        assert_eq!(None, none.unwrap());
+
+        let invalid: &[u8] = b"<table><tr><td>\xff</td></tr></table>";
+        let err = Table::find_first_from_reader(invalid).unwrap_err();
+        assert_eq!(std::io::ErrorKind::InvalidData, err.kind());
+    }
+
+    #[test]
+    fn test_without_empty_rows() {
+        let table = Table::find_first(TABLE_EMPTY_ROWS).unwrap();
+        assert_eq!(4, table.len());
+        let table = table.without_empty_rows();
+        assert_eq!(2, table.len());
+        let rows = table.iter().map(|row| row.as_slice()).collect::<Vec<_>>();
+        assert_eq!(vec![&["John", "20"], &["", "30"]], rows);
+        assert_eq!(Some("30"), table.row(1).unwrap().get_text("Age"));
+        assert_eq!(1, table.footer().count());
+
+        let table = Table::find_first(TABLE_COMPLEX).unwrap();
+        assert_eq!(3, table.without_empty_rows().len());
+    }
+
+    #[test]
+    fn test_trim() {
+        let html = "<table><tr><td>  keep me  </td></tr></table>";
+        let table = Table::find_first(html).unwrap();
+        assert_eq!(&["keep me"], table.iter().next().unwrap().as_slice());
+
+        let table = TableBuilder::new().trim(false).find_first(html).unwrap();
+        assert_eq!(&["  keep me  "], table.iter().next().unwrap().as_slice());
+
+        let table = TableBuilder::new()
+            .trim(false)
+            .collapse_whitespace(true)
+            .find_first(html)
+            .unwrap();
+        assert_eq!(&[" keep me "], table.iter().next().unwrap().as_slice());
+    }
+
+    #[test]
+    fn test_find_first_with() {
+        let table = Table::find_first_with(TABLE_MARKUP, |cell| {
+            cell.text().collect::<String>().to_uppercase()
+        })
+        .unwrap();
+        assert_eq!(
+            Some("JOHN SMITH"),
+            table.iter().next().unwrap().get("NAME")
+        );
+        assert_eq!(None, table.iter().next().unwrap().get("Name"));
+        assert_eq!(
+            Some("John <b>Smith</b>"),
+            table.iter().next().unwrap().get_html("NAME")
+        );
+        assert_eq!(None, Table::find_first_with(HTML_NO_TABLE, |_| todo!()));
+    }
+
+    #[test]
+    fn test_cell_attrs() {
+        let table = Table::find_first(TABLE_ROWSPAN).unwrap();
+        let row = table.iter().next().unwrap();
+        assert_eq!(Some("2"), row.get_attr("Category", "rowspan"));
+        assert_eq!(None, row.get_attr("Category", "colspan"));
+        assert_eq!(None, row.get_attr("foo", "rowspan"));
+        let row = table.iter().nth(1).unwrap();
+        assert_eq!(Some("2"), row.get_attr("Category", "rowspan"));
+        assert_eq!(1, row.cell("Category").unwrap().attrs().len());
+    }
+
+    #[test]
+    fn test_to_html() {
+        let table = Table::find_first(TABLE_TH_TD).unwrap();
+        assert_eq!(
+            "<table><tr><th>Name</th><th>Age</th></tr>\
+             <tr><td>John</td><td>20</td></tr></table>",
+            table.to_html()
+        );
+        assert_eq!(table, Table::find_first(&table.to_html()).unwrap());
+
+        let table = Table::find_first(TABLE_THEAD).unwrap();
+        assert_eq!(
+            "<table><tr><th>Name</th><th>City</th><th>Zip</th></tr>\
+             <tr><td>John</td><td>Boston</td><td>02108</td></tr>\
+             <tr><td>May</td><td>Denver</td><td>80202</td></tr></table>",
+            table.to_html()
+        );
+
+        let table = Table::find_first(TABLE_TD).unwrap();
+        assert_eq!(
+            "<table><tr><td>Name</td><td>Age</td></tr></table>",
+            table.to_html()
+        );
+
+        let html =
+            r#"<table><tr><th colspan="2">A</th><th>B</th></tr></table>"#;
+        let table = Table::find_first(html).unwrap();
+        assert_eq!(html, table.to_html());
+
+        let html = r#"<table><tr><th>A</th><th colspan="2">B</th></tr><tr><td>1</td><td>2</td><td>3</td></tr></table>"#;
+        let table = Table::find_first(html).unwrap();
+        assert_eq!(html, table.to_html());
+        let round_trip = Table::find_first(&table.to_html()).unwrap();
+        assert_eq!(table.header_row(), round_trip.header_row());
+        assert_eq!(table.data(), round_trip.data());
+    }
+
+    #[test]
+    fn test_to_html_escapes() {
+        let html = "<table><tr><th>A &amp; B</th></tr>\
+                    <tr><td>x &lt; \"y\"</td></tr></table>";
+        let table = TableBuilder::new().text_only(true).find_first(html);
+        let table = table.unwrap();
+        assert_eq!(
+            Some("x < \"y\""),
+            table.iter().next().unwrap().get("A & B")
+        );
+        let html = table.to_html();
+        assert_eq!(
+            "<table><tr><th>A &amp; B</th></tr>\
+             <tr><td>x &lt; &quot;y&quot;</td></tr></table>",
+            html
+        );
+        let round_trip = TableBuilder::new().text_only(true).find_first(&html);
+        assert_eq!(table.iter().next(), round_trip.unwrap().iter().next());
+    }
+
+    #[test]
+    fn test_find_nth() {
+        let first = Table::find_by_id(HTML_TWO_TABLES, "first").unwrap();
+        let second = Table::find_by_id(HTML_TWO_TABLES, "second").unwrap();
+        assert_eq!(Some(&first), Table::find_nth(HTML_TWO_TABLES, 0).as_ref());
+        assert_eq!(Some(&second), Table::find_nth(HTML_TWO_TABLES, 1).as_ref());
+        assert_eq!(None, Table::find_nth(HTML_TWO_TABLES, 2));
+        assert_eq!(None, Table::find_nth(HTML_NO_TABLE, 0));
+
+        let table = TableBuilder::new()
+            .text_only(true)
+            .find_nth(HTML_TWO_TABLES, 1)
+            .unwrap();
+        assert_eq!(second, table);
+    }
+
+    #[test]
+    fn test_row_get_normalized() {
+        let html = r#"
+<table>
+    <tr><th>Name </th><th>AGE</th><th> name</th></tr>
+    <tr><td>John</td><td>20</td><td>Smith</td></tr>
+</table>
+"#;
+        let table = TableBuilder::new().trim(false).find_first(html).unwrap();
+        let row = table.iter().next().unwrap();
+        assert_eq!(None, row.get("Name"));
+        assert_eq!(Some("John"), row.get("Name "));
+        assert_eq!(Some("John"), row.get_normalized("name"));
+        assert_eq!(Some("John"), row.get_normalized("  NAME\t"));
+        assert_eq!(Some("20"), row.get_normalized("Age "));
+        assert_eq!(None, row.get_normalized("foo"));
+    }
+
+    #[derive(Debug, PartialEq)]
+    struct Person {
+        name: String,
+        age: u32,
+    }
+
+    impl FromRow for Person {
+        fn from_row(row: &Row) -> Result<Person, RowError> {
+            Ok(Person {
+                name: row.try_get("Name")?.to_string(),
+                age: row.try_parse("Age")?,
+            })
+        }
+    }
+
+    #[test]
+    fn test_deserialize() {
+        let table = Table::find_first(TABLE_COMPLEX).unwrap();
+        let people = table.deserialize::<Person>().collect::<Vec<_>>();
+        assert_eq!(
+            vec![
+                Ok(Person {
+                    name: "John".to_string(),
+                    age: 20,
+                }),
+                Ok(Person {
+                    name: "May".to_string(),
+                    age: 30,
+                }),
+                Err(RowError::MissingColumn("Name".to_string())),
+                Err(RowError::InvalidValue {
+                    header: "Age".to_string(),
+                    value: "b".to_string(),
+                    message: "invalid digit found in string".to_string(),
+                }),
+            ],
+            people
+        );
+        assert_eq!(
+            "invalid value \"b\" in column Age: invalid digit found in string",
+            people[3].as_ref().unwrap_err().to_string()
+        );
+    }
+
+    #[test]
+    fn test_col_names() {
+        let table = Table::find_first(TABLE_COLS).unwrap();
+        assert!(table.headers().is_empty());
+
+        let builder = TableBuilder::new().col_names(true);
+        let table = builder.find_first(TABLE_COLS).unwrap();
+        assert_eq!(vec!["name", "score", "notes"], table.header_names());
+        let row = table.iter().next().unwrap();
+        assert_eq!(Some("John"), row.get("name"));
+        assert_eq!(Some("1"), row.get("score"));
+        assert_eq!(Some("d"), row.get("notes"));
+
+        let table = builder.find_first(TABLE_COLS_TH).unwrap();
+        assert_eq!(vec!["Name", "Age"], table.header_names());
+        assert_eq!(1, table.len());
+    }
+
+    #[test]
+    fn test_line_breaks() {
+        let builder = TableBuilder::new().text_only(true);
+        let table = builder.find_first(TABLE_LINE_BREAKS).unwrap();
+        let row = table.iter().next().unwrap();
+        assert_eq!(Some("1 Main StSpringfield"), row.get("Address"));
+
+        let builder = builder.line_breaks(true);
+        let table = builder.find_first(TABLE_LINE_BREAKS).unwrap();
+        let row = table.iter().next().unwrap();
+        assert_eq!(Some("1 Main St\nSpringfield"), row.get("Address"));
+        assert_eq!(Some("First   note\nSecond note"), row.get("Notes"));
+
+        let builder = builder.collapse_whitespace(true);
+        let table = builder.find_first(TABLE_LINE_BREAKS).unwrap();
+        let row = table.iter().next().unwrap();
+        assert_eq!(Some("First note\nSecond note"), row.get("Notes"));
+        assert_eq!(Some("1 Main St<br>Springfield"), row.get_html("Address"));
+    }
+
+    #[test]
+    fn test_find_by_headers_contains() {
+        let html = r#"
+<table><tr><th>Name</th><th>Weight (kg)</th></tr></table>
+<table>
+    <tr><th>Name</th><th>Age (years)<sup>1</sup></th></tr>
+    <tr><td>John</td><td>20</td></tr>
+</table>
+"#;
+        assert_eq!(None, Table::find_by_headers(html, &["Name", "Age"]));
+        let table = Table::find_by_headers_contains(html, &["Name", "Age"]);
+        let table = table.unwrap();
+        assert_eq!(Some("20"), table.column_index(1).first().copied());
+        assert_eq!(
+            None,
+            Table::find_by_headers_contains(html, &["Name", "Height"])
+        );
+        assert_eq!(
+            Table::find_first(html),
+            Table::find_by_headers_contains::<&str>(html, &[])
+        );
+    }
+
+    #[test]
+    fn test_iter_with_source_row() {
+        let table = Table::find_first(TABLE_TD_TD).unwrap();
+        let indices: Vec<_> =
+            table.iter_with_source_row().map(|(i, _)| i).collect();
+        assert_eq!(vec![0, 1], indices);
+
+        let table = Table::find_first(TABLE_THEAD).unwrap();
+        let rows: Vec<_> = table
+            .iter_with_source_row()
+            .map(|(i, row)| (i, row.get_index(0).unwrap()))
+            .collect();
+        assert_eq!(vec![(2, "John"), (3, "May")], rows);
+
+        let table = Table::find_first(TABLE_EMPTY_ROWS).unwrap();
+        let table = table.without_empty_rows();
+        let indices: Vec<_> =
+            table.iter_with_source_row().map(|(i, _)| i).collect();
+        assert_eq!(vec![2, 4], indices);
+    }
+
+    #[test]
+    fn test_find_by_id_special_characters() {
+        let cases = [
+            ("a:b", "colon"),
+            ("a.b", "dot"),
+            ("my table", "space"),
+            ("1st", "digit"),
+            ("-", "dash"),
+        ];
+        for &(id, content) in &cases {
+            let table = Table::find_by_id(HTML_SPECIAL_IDS, id).unwrap();
+            assert_eq!(Some(content), table.column_index(0).first().copied());
+        }
+        assert_eq!(None, Table::find_by_id(HTML_SPECIAL_IDS, "a"));
+        assert_eq!(None, Table::find_by_id(HTML_SPECIAL_IDS, "my"));
+    }
+
+    #[test]
+    fn test_escape_css_ident() {
+        assert_eq!("foo-bar_1", escape_css_ident("foo-bar_1"));
+        assert_eq!("a\\:b\\.c\\ d", escape_css_ident("a:b.c d"));
+        assert_eq!("\\31 st", escape_css_ident("1st"));
+        assert_eq!("-\\32 ", escape_css_ident("-2"));
+        assert_eq!("\\-", escape_css_ident("-"));
+        assert_eq!("caf\u{e9}", escape_css_ident("caf\u{e9}"));
+    }
+
+    #[test]
+    fn test_data() {
+        let table = Table::find_first(TABLE_COMPLEX).unwrap();
+        let expected = vec![
+            vec!["John", "20"],
+            vec!["May", "30", "foo"],
+            vec![],
+            vec!["a", "b", "c", "d"],
+        ];
+        assert_eq!(expected, table.data());
+        assert_eq!(expected, table.into_data());
+
+        let table = Table::find_first(TABLE_TH).unwrap();
+        assert!(table.data().is_empty());
+    }
+
+    #[test]
+    fn test_headers_sorted() {
+        let table = Table::find_first(TABLE_TH).unwrap();
+        assert_eq!(vec![("Name", 0), ("Age", 1)], table.headers_sorted());
+
+        let html = r#"<table><tr><th colspan="2">A</th><th>B</th><th>A</th></tr></table>"#;
+        let table = Table::find_first(html).unwrap();
+        assert_eq!(vec![("A", 0), ("B", 2), ("A", 3)], table.headers_sorted());
+
+        let table = Table::find_first(TABLE_TD).unwrap();
+        assert!(table.headers_sorted().is_empty());
+    }
+
+    #[test]
+    fn test_treat_first_row_as_data() {
+        let builder = TableBuilder::new().treat_first_row_as_data(true);
+        let table = builder.find_first(TABLE_TH_TD).unwrap();
+        assert!(table.headers().is_empty());
+        let rows: Vec<_> = table.iter().map(|row| row.as_slice()).collect();
+        assert_eq!(vec![&["Name", "Age"], &["John", "20"]], rows);
+
+        let table = builder.find_first(TABLE_THEAD).unwrap();
+        assert_eq!(vec!["Name", "City", "Zip"], table.header_names());
+        assert_eq!(2, table.len());
     }
 
     #[test]
-    fn test_find_by_id_none() {
-        assert_eq!(None, Table::find_by_id("", ""));
-        assert_eq!(None, Table::find_by_id("foo", "id"));
-        assert_eq!(None, Table::find_by_id(HTML_NO_TABLE, "id"));
+    fn test_header_row() {
+        let html = r#"
+<table>
+    <tr><th colspan="2">People</th></tr>
+    <tr><td colspan="2">Updated daily</td></tr>
+    <tr><th>Name</th><td>Age</td></tr>
+    <tr><td>John</td><td>20</td></tr>
+    <tfoot><tr><td>Total</td><td>1</td></tr></tfoot>
+</table>
+"#;
+        let table = TableBuilder::new().header_row(2).find_first(html).unwrap();
+        assert_eq!(vec!["Name", "Age"], table.header_names());
+        assert_eq!(1, table.len());
+        assert_eq!(Some("20"), table.iter().next().unwrap().get("Age"));
+        assert_eq!(1, table.footer().count());
+        let source: Vec<_> =
+            table.iter_with_source_row().map(|(i, _)| i).collect();
+        assert_eq!(vec![3], source);
 
-        assert_eq!(None, Table::find_by_id(TABLE_EMPTY, "id"));
-        assert_eq!(None, Table::find_by_id(TABLE_TH, "id"));
-        assert_eq!(None, Table::find_by_id(TABLE_TH, ""));
-        assert_eq!(None, Table::find_by_id(HTML_TWO_TABLES, "id"));
+        let table = TableBuilder::new().header_row(0).find_first(html).unwrap();
+        assert_eq!(vec!["People"], table.header_names());
+        assert_eq!(3, table.len());
+
+        let table = TableBuilder::new().header_row(4).find_first(html).unwrap();
+        assert!(table.headers().is_empty());
+        assert_eq!(4, table.len());
+
+        let table = TableBuilder::new().header_row(1).find_first(TABLE_THEAD);
+        let table = table.unwrap();
+        assert_eq!(vec!["Name", "City", "Zip"], table.header_names());
+        assert_eq!(2, table.len());
+    }
+
+    #[test]
+    fn test_infer_schema() {
+        let html = r#"
+<table>
+    <tr><th>A</th><th>B</th><th>C</th><th>D</th><th>E</th><th>F</th></tr>
+    <tr><td>1</td><td>1.5</td><td>true</td><td>x</td><td></td></tr>
+    <tr><td> -2 </td><td>3</td><td>false</td><td>4</td><td></td></tr>
+</table>
+"#;
+        let table = Table::find_first(html).unwrap();
+        assert_eq!(
+            vec![
+                ColumnType::Integer,
+                ColumnType::Float,
+                ColumnType::Boolean,
+                ColumnType::Text,
+                ColumnType::Text,
+                ColumnType::Text,
+            ],
+            table.infer_schema()
+        );
+
+        let table = Table::find_first(TABLE_COMPLEX).unwrap();
+        assert_eq!(4, table.infer_schema().len());
+        assert!(Table::find_first(TABLE_EMPTY)
+            .unwrap()
+            .infer_schema()
+            .is_empty());
+    }
+
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn test_find_all_parallel() {
+        for html in &[HTML_NO_TABLE, HTML_TWO_TABLES, TABLE_NESTED, TABLE_THEAD]
+        {
+            assert_eq!(Table::find_all(html), Table::find_all_parallel(html));
+        }
+    }
+
+    #[test]
+    fn test_row_eq_and_hash() {
+        use std::collections::HashSet;
+
+        let first = Table::find_by_id(HTML_TWO_TABLES, "first").unwrap();
+        let second = Table::find_by_id(HTML_TWO_TABLES, "second").unwrap();
+        let td = Table::find_first(TABLE_TD).unwrap();
+        let a = first.iter().next().unwrap();
+        let b = TableBuilder::new()
+            .text_only(true)
+            .find_by_id(HTML_TWO_TABLES, "first")
+            .unwrap();
+        let b = b.iter().next().unwrap();
+        assert_eq!(a, b);
+        assert_ne!(a, second.iter().next().unwrap());
+
+        let set: HashSet<Row> =
+            first.iter().chain(td.iter()).chain(Some(b)).collect();
+        assert_eq!(2, set.len());
+
+        let owned: HashSet<OwnedRow> =
+            first.clone().into_iter().chain(first).collect();
+        assert_eq!(1, owned.len());
+    }
+
+    #[test]
+    fn test_skip_hidden_rows() {
+        let table = Table::find_first(TABLE_HIDDEN_ROWS).unwrap();
+        assert_eq!(vec!["John", "May", "Ann", "Bob"], table.column_index(0));
+
+        let builder = TableBuilder::new().skip_hidden_rows(true);
+        let table = builder.find_first(TABLE_HIDDEN_ROWS).unwrap();
+        assert_eq!(vec!["John", "Bob"], table.column_index(0));
+        let source: Vec<_> =
+            table.iter_with_source_row().map(|(i, _)| i).collect();
+        assert_eq!(vec![1, 4], source);
+    }
+
+    #[test]
+    fn test_cell_span() {
+        let html = r#"
+<table>
+    <tr><td rowspan="2" colspan="2">a</td><td>b</td></tr>
+    <tr><td>c</td></tr>
+</table>
+"#;
+        let spanned = |row_offset, col_offset| CellSpan::Spanned {
+            row_offset,
+            col_offset,
+        };
+        for fill in &[SpanFill::Duplicate, SpanFill::Empty] {
+            let table = TableBuilder::new().span_fill(*fill).find_first(html);
+            let table = table.unwrap();
+            let spans: Vec<Vec<_>> = table
+                .iter()
+                .map(|row| {
+                    (0..row.len()).filter_map(|i| row.span_index(i)).collect()
+                })
+                .collect();
+            assert_eq!(
+                vec![
+                    vec![CellSpan::Origin, spanned(0, 1), CellSpan::Origin],
+                    vec![spanned(1, 0), spanned(1, 1), CellSpan::Origin],
+                ],
+                spans
+            );
+        }
+        let table = Table::find_first(html).unwrap();
+        let row = table.row(1).unwrap();
+        assert!(!row.cell_index(0).unwrap().is_origin());
+        assert!(row.cell_index(2).unwrap().is_origin());
+        assert_eq!(None, row.span_index(3));
+    }
+
+    #[test]
+    fn test_find_by_headers_indexed() {
+        let html = HTML_CLASSES;
+        let (index, table) =
+            Table::find_by_headers_indexed(html, &["Weight"]).unwrap();
+        assert_eq!(2, index);
+        assert_eq!(Table::find_nth(html, index).unwrap(), table);
+        assert_eq!(
+            Some(0),
+            Table::find_by_headers_indexed::<&str>(html, &[]).map(|(i, _)| i)
+        );
+        assert_eq!(None, Table::find_by_headers_indexed(html, &["Height"]));
+    }
+
+    #[test]
+    fn test_trim_nbsp_and_invisible_characters() {
+        let html = "<table><tr><td>&nbsp; \u{200B}x&nbsp;y\u{FEFF}&nbsp;</td></tr></table>";
+        let table = Table::find_first(html).unwrap();
+        assert_eq!(&["x&nbsp;y"], table.iter().next().unwrap().as_slice());
+
+        let table = TableBuilder::new().text_only(true).find_first(html);
+        let table = table.unwrap();
+        assert_eq!(&["x\u{A0}y"], table.iter().next().unwrap().as_slice());
+
+        let table = TableBuilder::new().trim(false).find_first(html).unwrap();
+        assert_eq!(
+            &["&nbsp; \u{200B}x&nbsp;y\u{FEFF}&nbsp;"],
+            table.iter().next().unwrap().as_slice()
+        );
+    }
+
+    #[test]
+    fn test_row_get_nonempty_and_trimmed() {
+        let html = r#"
+<table>
+    <tr><th>A</th><th>B</th><th>C</th></tr>
+    <tr><td></td><td>  </td><td> x </td></tr>
+</table>
+"#;
+        let table = TableBuilder::new().trim(false).find_first(html).unwrap();
+        let row = table.iter().next().unwrap();
+        assert_eq!(None, row.get_nonempty("A"));
+        assert_eq!(Some("  "), row.get_nonempty("B"));
+        assert_eq!(Some(" x "), row.get_nonempty("C"));
+        assert_eq!(None, row.get_trimmed("A"));
+        assert_eq!(None, row.get_trimmed("B"));
+        assert_eq!(Some("x"), row.get_trimmed("C"));
+        assert_eq!(None, row.get_nonempty("D"));
+        assert_eq!(None, row.get_trimmed("D"));
+    }
+
+    #[test]
+    fn test_concat() {
+        let first = Table::find_by_id(HTML_TWO_TABLES, "first").unwrap();
+        let other = Table::find_first(TABLE_COMPLEX).unwrap();
+        let table = Table::concat(&[first.clone(), first.clone()]).unwrap();
+        assert_eq!(2, table.len());
+        assert_eq!(vec!["Name", "Age"], table.header_names());
+        assert_eq!(vec!["John", "John"], table.column("Name").unwrap());
+
+        let table = Table::concat(std::slice::from_ref(&first)).unwrap();
+        assert_eq!(first, table);
+
+        let tfoot = Table::find_first(TABLE_TFOOT).unwrap();
+        let table = Table::concat(&[tfoot.clone(), tfoot.clone()]).unwrap();
+        assert_eq!(2 * tfoot.len(), table.len());
+        assert_eq!(2 * tfoot.footer().count(), table.footer().count());
+
+        assert_eq!(Err(TableError::NoTableFound), Table::concat(&[]));
+        let error = Table::concat(&[first.clone(), first, other]).unwrap_err();
+        assert_eq!(
+            TableError::HeaderMismatch {
+                index: 2,
+                expected: vec!["Name".to_string(), "Age".to_string()],
+                found: vec![
+                    "Name".to_string(),
+                    "Age".to_string(),
+                    "Extra".to_string()
+                ],
+            },
+            error
+        );
+        assert_eq!(
+            "headers of table 2 do not match: expected [\"Name\", \"Age\"], \
+             found [\"Name\", \"Age\", \"Extra\"]",
+            error.to_string()
+        );
+    }
+
+    #[test]
+    fn test_transpose() {
+        let table = Table::find_first(TABLE_COMPLEX).unwrap().transpose();
+        assert!(table.headers().is_empty());
+        let rows: Vec<_> = table.iter().map(|row| row.as_slice()).collect();
+        assert_eq!(
+            vec![
+                &["Name", "John", "May", "", "a"],
+                &["Age", "20", "30", "", "b"],
+                &["Extra", "", "foo", "", "c"],
+                &["", "", "", "", "d"],
+            ],
+            rows
+        );
+        let source: Vec<_> =
+            table.iter_with_source_row().map(|(i, _)| i).collect();
+        assert_eq!(vec![0, 1, 2, 3], source);
+
+        let table = Table::find_first(TABLE_LINKS).unwrap().transpose();
+        let row = table.row(0).unwrap();
+        assert_eq!(vec!["/person/42"], row.cell_index(1).unwrap().links());
+
+        let table = Table::find_first(TABLE_EMPTY).unwrap();
+        assert!(table.transpose().is_empty());
+        assert!(table.transpose_with_headers().is_empty());
+    }
+
+    #[test]
+    fn test_transpose_with_headers() {
+        let table = Table::find_first(TABLE_TD_TD).unwrap();
+        let transposed = table.transpose_with_headers();
+        assert_eq!(table.column_index(0), transposed.header_names());
+        assert_eq!(1, transposed.len());
+        let row = transposed.row(0).unwrap();
+        assert_eq!(Some("20"), row.get("John"));
+    }
+
+    #[test]
+    fn test_id_and_classes() {
+        let table = Table::find_by_headers(HTML_TWO_TABLES, &["Weight"]);
+        assert_eq!(Some("second"), table.unwrap().id());
+
+        let table = Table::find_by_headers(HTML_CLASSES, &["Weight"]).unwrap();
+        assert_eq!(None, table.id());
+        assert_eq!(vec!["data", "other"], table.classes());
+
+        let table = Table::find_first(TABLE_TH).unwrap();
+        assert_eq!(None, table.id());
+        assert!(table.classes().is_empty());
+    }
+
+    #[test]
+    fn test_header_in_tbody() {
+        let table = Table::find_first(TABLE_HEADER_IN_TBODY).unwrap();
+        assert_eq!(vec!["Name", "Age"], table.header_names());
+        assert_eq!(vec!["John", "May"], table.column("Name").unwrap());
+        let source: Vec<_> =
+            table.iter_with_source_row().map(|(i, _)| i).collect();
+        assert_eq!(vec![0, 2], source);
+    }
+
+    #[test]
+    fn test_mixed_first_row() {
+        let table = Table::find_first(TABLE_MIXED_FIRST_ROW).unwrap();
+        assert!(table.headers().is_empty());
+        assert_eq!(2, table.len());
+        assert_eq!(Some("John"), table.row(0).unwrap().row_header());
+
+        let table = Table::find_first(TABLE_ROW_HEADERS).unwrap();
+        assert_eq!(vec!["", "Speed", "Price"], table.header_names());
+    }
+
+    #[test]
+    fn test_column_widths() {
+        let html = r#"
+<table>
+    <tr><th>Name</th><th colspan="2">Address</th></tr>
+    <tr><td>Zoë</td><td>1</td><td>Rue</td></tr>
+    <tr><td>Zéro</td><td>one<br>two</td><td></td><td>x</td></tr>
+</table>
+"#;
+        let table = TableBuilder::new()
+            .text_only(true)
+            .line_breaks(true)
+            .find_first(html)
+            .unwrap();
+        assert_eq!(vec![5, 7, 3, 1], table.column_widths());
+
+        let table = Table::find_first(TABLE_EMPTY).unwrap();
+        assert!(table.column_widths().is_empty());
+    }
+
+    #[test]
+    fn test_to_ascii() {
+        let html = r#"
+<table>
+    <tr><th>Name</th><th colspan="2">Address</th></tr>
+    <tr><td>Zoë</td><td>1</td><td>Rue</td></tr>
+    <tr><td>Bob</td><td>one<br>two</td><td></td><td>x</td></tr>
+</table>
+"#;
+        let table = TableBuilder::new()
+            .text_only(true)
+            .line_breaks(true)
+            .find_first(html)
+            .unwrap();
+        let expected = "\
++------+---------+-----+---+
+| Name | Address |     |   |
++------+---------+-----+---+
+| Zoë  | 1       | Rue |   |
+| Bob  | one     |     | x |
+|      | two     |     |   |
++------+---------+-----+---+
+";
+        assert_eq!(expected, table.to_ascii());
+
+        let html = "<table><tr><th>A</th><th>B</th></tr></table>";
+        let table = Table::find_first(html).unwrap();
+        assert_eq!("+---+---+\n| A | B |\n+---+---+\n", table.to_ascii());
+
+        let table = Table::find_first(TABLE_EMPTY).unwrap();
+        assert_eq!("", table.to_ascii());
+    }
+
+    #[test]
+    fn test_rows_where() {
+        let table = Table::find_first(TABLE_COMPLEX).unwrap();
+        let rows: Vec<_> = table
+            .rows_where(|r| r.get("Extra").is_some())
+            .map(|r| r.as_slice())
+            .collect();
+        assert_eq!(2, rows.len());
+        assert_eq!(&["May", "30", "foo"], rows[0]);
+        assert_eq!(&["a", "b", "c", "d"], rows[1]);
+        assert_eq!(0, table.rows_where(|_| false).count());
+        assert_eq!(table.len(), table.rows_where(|_| true).count());
+    }
+
+    #[test]
+    fn test_ragged_rows() {
+        let table = Table::find_first(TABLE_COMPLEX).unwrap();
+        assert_eq!(vec![0, 2, 3], table.ragged_rows());
+        assert!(!table.is_rectangular());
+
+        let table = Table::find_first(TABLE_TD_TD).unwrap();
+        assert!(table.ragged_rows().is_empty());
+        assert!(table.is_rectangular());
+
+        let html = r#"
+<table>
+    <tr><th>Name</th><th colspan="2">Address</th></tr>
+    <tr><td>John</td><td>1</td><td>Rue</td></tr>
+    <tr><td>May</td><td>2</td></tr>
+</table>
+"#;
+        let table = Table::find_first(html).unwrap();
+        assert_eq!(vec![1], table.ragged_rows());
+
+        let html = r#"
+<table>
+    <tr><td>a</td></tr>
+    <tr><td>b</td><td>c</td></tr>
+    <tr><td>d</td></tr>
+    <tr><td>e</td><td>f</td><td>g</td></tr>
+</table>
+"#;
+        let table = Table::find_first(html).unwrap();
+        assert_eq!(vec![1, 3], table.ragged_rows());
+
+        let table = Table::find_first(TABLE_EMPTY).unwrap();
+        assert!(table.is_rectangular());
+    }
+
+    #[test]
+    fn test_find_first_from_document() {
+        let table = Table::find_first_from_document(HTML_TWO_TABLES).unwrap();
+        assert_eq!(Some("first"), table.id());
+        assert_eq!(Table::find_first(HTML_TWO_TABLES), Some(table));
+
+        let table = Table::find_first_from_document(TABLE_TH_TD).unwrap();
+        assert_eq!(Table::find_first(TABLE_TH_TD), Some(table));
+
+        assert_eq!(None, Table::find_first_from_document(""));
+    }
+
+    #[test]
+    fn test_implicit_tbody() {
+        let rows = "<tr><th>Name</th><th>Age</th></tr>\
+                    <tr><td>John</td><td>20</td></tr>";
+        let head = "<tr><th>Name</th><th>Age</th></tr>";
+        let body = "<tr><td>John</td><td>20</td></tr>";
+        let foot = "<tfoot><tr><td>Total</td><td>20</td></tr></tfoot>";
+        let pairs = [
+            (
+                format!("<table>{}</table>", rows),
+                format!("<table><tbody>{}</tbody></table>", rows),
+            ),
+            (
+                format!("<table><thead>{}</thead>{}</table>", head, body),
+                format!(
+                    "<table><thead>{}</thead><tbody>{}</tbody></table>",
+                    head, body
+                ),
+            ),
+            (
+                format!("<table>{}{}</table>", rows, foot),
+                format!("<table><tbody>{}</tbody>{}</table>", rows, foot),
+            ),
+        ];
+        for (implicit, explicit) in &pairs {
+            let table = Table::find_first(implicit).unwrap();
+            assert_eq!(vec!["Name", "Age"], table.header_names());
+            assert_eq!(&[vec!["John", "20"]], table.data());
+            assert_eq!(Table::find_first(explicit).as_ref(), Some(&table));
+            assert_eq!(
+                Table::find_first_from_document(implicit).as_ref(),
+                Some(&table)
+            );
+            assert_eq!(
+                Table::find_first_from_document(explicit).as_ref(),
+                Some(&table)
+            );
+        }
+    }
+
+    #[test]
+    fn test_table_header_row() {
+        let html = r#"
+<table>
+    <tr><td></td><th colspan="2"><a href="/addr">Address</a></th></tr>
+    <tr><th>John</th><td>1</td><td>Rue</td></tr>
+</table>
+"#;
+        let table = Table::find_first(html).unwrap();
+        let header = table.header_row().unwrap();
+        let address = r#"<a href="/addr">Address</a>"#;
+        assert_eq!(&["", address, address], header.as_slice());
+        assert_eq!(vec!["/addr"], header.get_links(address));
+        assert_eq!(
+            Some(CellSpan::Spanned {
+                row_offset: 0,
+                col_offset: 1
+            }),
+            header.span_index(2)
+        );
+        assert_eq!(None, header.row_header());
+
+        let table = Table::find_first(TABLE_TD_TD).unwrap();
+        assert_eq!(None, table.header_row());
+
+        let html = r#"
+<table>
+    <colgroup><col id="name"><col><col class="age"></colgroup>
+    <tr><td>John</td><td>x</td><td>20</td></tr>
+</table>
+"#;
+        let table = TableBuilder::new()
+            .col_names(true)
+            .find_first(html)
+            .unwrap();
+        let header = table.header_row().unwrap();
+        assert_eq!(&["name", "", "age"], header.as_slice());
+
+        let table = Table::find_first(TABLE_TH_TD)
+            .unwrap()
+            .transpose_with_headers();
+        let header = table.header_row().unwrap();
+        assert_eq!(table.header_names(), header.as_slice());
+    }
+
+    #[test]
+    fn test_value_counts() {
+        let table = Table::find_first(TABLE_COMPLEX).unwrap();
+        let counts = table.value_counts("Extra").unwrap();
+        assert_eq!(2, counts.len());
+        assert_eq!(Some(&1), counts.get("foo"));
+        assert_eq!(Some(&1), counts.get("c"));
+        assert_eq!(3, table.value_counts("Name").unwrap().len());
+        assert_eq!(None, table.value_counts("Missing"));
+
+        let table = Table::find_first(TABLE_TH).unwrap();
+        assert!(table.value_counts("Name").unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_cell_selector() {
+        let html = r#"
+<table>
+    <tr><th>Name</th><th class="note">Note</th><th>Age</th></tr>
+    <tr><td>John</td><td class="note">new</td><td>20</td></tr>
+    <tfoot><tr><td>Total</td><td class="note"></td><td>20</td></tr></tfoot>
+</table>
+"#;
+        let table = TableBuilder::new()
+            .cell_selector(":not(.note)")
+            .unwrap()
+            .find_first(html)
+            .unwrap();
+        assert_eq!(vec!["Name", "Age"], table.header_names());
+        assert_eq!(&[vec!["John", "20"]], table.data());
+        let footer: Vec<_> = table.footer().map(|row| row.as_slice()).collect();
+        assert_eq!(vec![&["Total", "20"]], footer);
+
+        let table = TableBuilder::new()
+            .cell_selector("td")
+            .unwrap()
+            .find_first(TABLE_ROW_HEADERS)
+            .unwrap();
+        assert!(table.iter().all(|row| row.row_header().is_none()));
+
+        assert_eq!(
+            Err(TableError::InvalidSelector("td[".to_string())),
+            TableBuilder::new().cell_selector("td[")
+        );
+    }
+
+    #[test]
+    fn test_for_each_row() {
+        for html in &[TABLE_COMPLEX, TABLE_ROWSPAN, TABLE_ROW_HEADERS] {
+            let table = Table::find_first(html).unwrap();
+            let mut rows = Vec::new();
+            Table::for_each_row(html, |row| {
+                assert_eq!(&table.headers, row.headers);
+                rows.push((
+                    row.as_slice().to_vec(),
+                    row.row_header().map(String::from),
+                ));
+            });
+            let expected: Vec<_> = table
+                .iter()
+                .map(|row| {
+                    (
+                        row.as_slice().to_vec(),
+                        row.row_header().map(String::from),
+                    )
+                })
+                .collect();
+            assert_eq!(expected, rows);
+        }
+
+        let mut called = false;
+        Table::for_each_row(TABLE_EMPTY, |_| called = true);
+        Table::for_each_row("<p>no table</p>", |_| called = true);
+        assert!(!called);
+
+        let mut names = Vec::new();
+        TableBuilder::new()
+            .treat_first_row_as_data(true)
+            .for_each_row(TABLE_TH_TD, |row| {
+                names.push(row.get_index(0).unwrap().to_string());
+            });
+        assert_eq!(vec!["Name", "John"], names);
+    }
+
+    #[test]
+    fn test_normalize_headers() {
+        let html = r#"
+<table>
+    <tr>
+        <th> Name </th><th>Age*</th><th>Height (cm) **</th><th>Age</th>
+    </tr>
+    <tr><td>John</td><td>20</td><td>180</td><td>21</td></tr>
+</table>
+"#;
+        let table = TableBuilder::new()
+            .normalize_headers(Some("*"))
+            .find_first(html)
+            .unwrap();
+        let row = table.iter().next().unwrap();
+        assert_eq!(Some("John"), row.get("Name"));
+        assert_eq!(Some("20"), row.get("Age"));
+        assert_eq!(Some("180"), row.get("Height (cm)"));
+        assert_eq!(None, row.get("Age*"));
+        assert_eq!(Some("20"), row.get_ci("age"));
+        assert_eq!(None, row.get_ci("AGE*"));
+        assert_eq!(Some("180"), row.get_normalized(" HEIGHT (CM) "));
+        assert_eq!(Some("John"), row.get_normalized("name"));
+        assert_eq!(
+            vec!["Name", "Age*", "Height (cm) **", "Age"],
+            table.header_names()
+        );
+
+        let table = TableBuilder::new()
+            .normalize_headers(Some(""))
+            .find_first(html)
+            .unwrap();
+        let row = table.iter().next().unwrap();
+        assert_eq!(Some("20"), row.get("Age*"));
+        assert_eq!(None, row.get("Height (cm)"));
+
+        let table = TableBuilder::new()
+            .normalize_headers(Some("*"))
+            .normalize_headers(None)
+            .find_first(html)
+            .unwrap();
+        assert_eq!(Some("21"), table.iter().next().unwrap().get("Age"));
+    }
+
+    #[test]
+    fn test_try_find_by_selector() {
+        for selector in &["table[", "", "#", "table >", "td:nth-child(x)"] {
+            let error = TableError::InvalidSelector(selector.to_string());
+            assert_eq!(
+                Err(error),
+                Table::try_find_by_selector(HTML_TWO_TABLES, selector)
+            );
+            assert_eq!(
+                None,
+                Table::find_by_selector(HTML_TWO_TABLES, selector)
+            );
+            let builder = TableBuilder::new();
+            assert_eq!(
+                None,
+                builder.find_by_selector(HTML_TWO_TABLES, selector)
+            );
+        }
+        assert_eq!(
+            Err(TableError::NoTableFound),
+            Table::try_find_by_selector(HTML_NO_TABLE, "table")
+        );
+        assert_eq!(
+            Table::find_by_id(HTML_TWO_TABLES, "second"),
+            Table::try_find_by_selector(HTML_TWO_TABLES, "#second").ok()
+        );
+    }
+
+    #[test]
+    fn test_find_by_any_header() {
+        let table = Table::find_by_any_header(HTML_TWO_TABLES, &["Weight"]);
+        assert_eq!(Table::find_by_id(HTML_TWO_TABLES, "second"), table);
+
+        let table = Table::find_by_any_header(
+            HTML_TWO_TABLES,
+            &["DOB", "Weight", "Age"],
+        );
+        assert_eq!(Table::find_by_id(HTML_TWO_TABLES, "first"), table);
+
+        let headers: [&str; 0] = [];
+        assert_eq!(None, Table::find_by_any_header(HTML_TWO_TABLES, &headers));
+        assert_eq!(None, Table::find_by_any_header(HTML_TWO_TABLES, &["DOB"]));
+        assert_eq!(None, Table::find_by_any_header(HTML_TWO_TABLES, &["age"]));
+    }
+
+    #[test]
+    fn test_table_cell() {
+        let table = Table::find_first(TABLE_COMPLEX).unwrap();
+        assert_eq!(Some("John"), table.cell(0, 0));
+        assert_eq!(None, table.cell(0, 2));
+        assert_eq!(Some("foo"), table.cell(1, 2));
+        assert_eq!(None, table.cell(2, 0));
+        assert_eq!(Some("d"), table.cell(3, 3));
+        assert_eq!(None, table.cell(4, 0));
+
+        let table = Table::find_first(TABLE_ROWSPAN).unwrap();
+        for (i, row) in table.iter().enumerate() {
+            for (j, cell) in row.iter().enumerate() {
+                assert_eq!(Some(cell.as_str()), table.cell(i, j));
+            }
+        }
+    }
+
+    #[test]
+    fn test_pad_rows() {
+        let html = r#"
+<table>
+    <tr><th>Name</th><th colspan="2">Address</th></tr>
+    <tr><td>John</td></tr>
+    <tr></tr>
+    <tr><td>May</td><td>1</td><td>Rue</td><td>extra</td></tr>
+    <tfoot><tr><td>Total</td></tr></tfoot>
+</table>
+"#;
+        let table =
+            TableBuilder::new().pad_rows(true).find_first(html).unwrap();
+        assert_eq!(
+            &[
+                vec!["John", "", ""],
+                vec!["", "", ""],
+                vec!["May", "1", "Rue", "extra"],
+            ],
+            table.data()
+        );
+        let row = table.row(1).unwrap();
+        assert_eq!(Some(""), row.get("Address"));
+        assert_eq!(Some(CellSpan::Origin), row.span_index(2));
+        let footer = table.footer().next().unwrap();
+        assert_eq!(&["Total", "", ""], footer.as_slice());
+        assert_eq!(vec![2], table.ragged_rows());
+
+        let mut rows = Vec::new();
+        TableBuilder::new()
+            .pad_rows(true)
+            .for_each_row(html, |row| rows.push(row.as_slice().to_vec()));
+        assert_eq!(table.data(), &rows[..]);
+
+        let table = TableBuilder::new()
+            .pad_rows(true)
+            .find_first(TABLE_TD)
+            .unwrap();
+        assert_eq!(Table::find_first(TABLE_TD).unwrap(), table);
+    }
+
+    #[test]
+    fn test_find_containing() {
+        let table = Table::find_containing(HTML_TWO_TABLES, "150");
+        assert_eq!(Table::find_by_id(HTML_TWO_TABLES, "second"), table);
+        let table = Table::find_containing(HTML_TWO_TABLES, "Weight");
+        assert_eq!(Table::find_by_id(HTML_TWO_TABLES, "second"), table);
+        let table = Table::find_containing(HTML_TWO_TABLES, "John");
+        assert_eq!(Table::find_by_id(HTML_TWO_TABLES, "first"), table);
+        assert_eq!(None, Table::find_containing(HTML_TWO_TABLES, "15"));
+        assert_eq!(None, Table::find_containing(HTML_NO_TABLE, "John"));
+
+        let table = Table::find_containing_substr(HTML_TWO_TABLES, "15");
+        assert_eq!(Table::find_by_id(HTML_TWO_TABLES, "second"), table);
+        let table = Table::find_containing_substr(HTML_TWO_TABLES, "");
+        assert_eq!(Table::find_by_id(HTML_TWO_TABLES, "first"), table);
+
+        let html = r#"
+<table>
+    <tr><td>outer<table id="inner"><tr><td>marker</td></tr></table></td></tr>
+</table>
+<table id="last"><tr><td>  last
+    marker&nbsp;</td></tr></table>
+"#;
+        let table = Table::find_containing(html, "marker").unwrap();
+        assert_eq!(Some("inner"), table.id());
+        let table = Table::find_containing(html, "last marker").unwrap();
+        assert_eq!(Some("last"), table.id());
+    }
+
+    #[test]
+    fn test_sorted_by() {
+        let html = r#"
+<table>
+    <tr><th>Name</th><th>Score</th></tr>
+    <tr><td>b</td><td>10</td></tr>
+    <tr><td>a</td></tr>
+    <tr><td>c</td><td>-1.5</td></tr>
+    <tr><td>d</td><td>n/a</td></tr>
+    <tr><td>e</td><td> 9 </td></tr>
+    <tr><td>f</td><td>10</td></tr>
+</table>
+"#;
+        let table = Table::find_first(html).unwrap();
+        fn names(rows: Vec<Row>) -> Vec<String> {
+            let names = rows.iter().map(|row| row.get("Name").unwrap());
+            names.map(String::from).collect()
+        }
+        assert_eq!(
+            vec!["c", "b", "f", "e", "d", "a"],
+            names(table.sorted_by("Score"))
+        );
+        assert_eq!(
+            vec!["c", "e", "b", "f", "d", "a"],
+            names(table.sorted_by_numeric("Score"))
+        );
+        assert_eq!(
+            vec!["a", "b", "c", "d", "e", "f"],
+            names(table.sorted_by("Name"))
+        );
+        assert_eq!(
+            vec!["b", "a", "c", "d", "e", "f"],
+            names(table.sorted_by("Missing"))
+        );
+        assert_eq!(Some("b"), table.row(0).unwrap().get("Name"));
+    }
+
+    #[test]
+    fn test_has_header_row() {
+        assert!(Table::find_first(TABLE_TH).unwrap().has_header_row());
+        assert!(Table::find_first(TABLE_TH_TD).unwrap().has_header_row());
+        assert!(!Table::find_first(TABLE_TD).unwrap().has_header_row());
+        assert!(!Table::find_first(TABLE_EMPTY).unwrap().has_header_row());
+
+        let html = "<table><tr><th></th></tr><tr><td>John</td></tr></table>";
+        let table = Table::find_first(html).unwrap();
+        assert!(table.has_header_row());
+        assert_eq!(vec![""], table.header_names());
+
+        let html = r#"
+<table>
+    <colgroup><col id="name"></colgroup>
+    <tr><td>John</td></tr>
+</table>
+"#;
+        let table = TableBuilder::new()
+            .col_names(true)
+            .find_first(html)
+            .unwrap();
+        assert!(!table.has_header_row());
+        assert!(!table.headers().is_empty());
+
+        let table = TableBuilder::new()
+            .treat_first_row_as_data(true)
+            .find_first(TABLE_TH_TD)
+            .unwrap();
+        assert!(!table.has_header_row());
+        let table = TableBuilder::new()
+            .header_row(0)
+            .find_first(TABLE_TD)
+            .unwrap();
+        assert!(table.has_header_row());
+
+        let table = Table::find_first(TABLE_TH_TD).unwrap();
+        assert!(!table.transpose().has_header_row());
+        assert!(table.transpose_with_headers().has_header_row());
     }
 
     #[test]
-    fn test_find_by_id_some() {
-        assert!(Table::find_by_id(HTML_TWO_TABLES, "first").is_some());
-        assert!(Table::find_by_id(HTML_TWO_TABLES, "second").is_some());
+    fn test_find_by_exact_headers() {
+        let html = r#"
+<table id="wide"><tr><th>Name</th><th>Age</th><th>City</th></tr></table>
+<table id="dup"><tr><th>Name</th><th>Name</th></tr></table>
+<table id="plain"><tr><td>Name</td><td>Age</td></tr></table>
+<table id="narrow"><tr><th>Age</th><th>Name</th></tr></table>
+"#;
+        let id = |headers: &[&str]| {
+            let table = Table::find_by_exact_headers(html, headers);
+            table.and_then(|t| t.id().map(String::from))
+        };
+        assert_eq!(Some("narrow".to_string()), id(&["Name", "Age"]));
+        assert_eq!(Some("wide".to_string()), id(&["City", "Name", "Age"]));
+        assert_eq!(Some("dup".to_string()), id(&["Name", "Name"]));
+        assert_eq!(Some("plain".to_string()), id(&[]));
+        assert_eq!(None, id(&["Name"]));
+        assert_eq!(None, id(&["Name", "Age", "Age"]));
+        assert_eq!(None, id(&["Name", "Age", "City", "Zip"]));
+
+        let table = Table::find_by_headers(html, &["Name", "Age"]).unwrap();
+        assert_eq!(Some("wide"), table.id());
     }
 
     #[test]
-    fn test_find_by_headers_empty() {
-        let headers: [&str; 0] = [];
+    fn test_num_columns() {
+        let html = r#"
+<table>
+    <tr><th>A</th><th>B</th><th>C</th></tr>
+    <tr><td colspan="2">1</td><td>2</td></tr>
+    <tr><td>3</td><td colspan="2">4</td></tr>
+</table>
+"#;
+        let table = Table::find_first(html).unwrap();
+        assert_eq!(3, table.num_columns());
+        assert!(table.is_rectangular());
+        assert_eq!(3, table.infer_schema().len());
 
-        assert_eq!(None, Table::find_by_headers("", &headers));
-        assert_eq!(None, Table::find_by_headers("foo", &headers));
-        assert_eq!(None, Table::find_by_headers(HTML_NO_TABLE, &headers));
+        let table = TableBuilder::new()
+            .expand_spans(false)
+            .find_first(html)
+            .unwrap();
+        assert_eq!(3, table.num_columns());
+        assert_eq!(vec![0, 1], table.ragged_rows());
 
-        assert!(Table::find_by_headers(TABLE_EMPTY, &headers).is_some());
-        assert!(Table::find_by_headers(HTML_TWO_TABLES, &headers).is_some());
+        let html = r#"<table><tr><td colspan="4">wide</td></tr></table>"#;
+        assert_eq!(4, Table::find_first(html).unwrap().num_columns());
+
+        let html =
+            r#"<table><tr><th>A</th><th colspan="2">B</th></tr></table>"#;
+        let table = Table::find_first(html).unwrap();
+        assert_eq!(3, table.num_columns());
+        assert_eq!(3, table.infer_schema().len());
+
+        assert_eq!(4, Table::find_first(TABLE_COMPLEX).unwrap().num_columns());
+        assert_eq!(0, Table::find_first(TABLE_EMPTY).unwrap().num_columns());
     }
 
     #[test]
-    fn test_find_by_headers_none() {
-        let headers = ["Name", "Age"];
-        let bad_headers = ["Name", "BAD"];
+    fn test_try_from() {
+        let html = Html::parse_document(HTML_TWO_TABLES);
+        let table = Table::try_from(&html).unwrap();
+        assert_eq!(Table::find_first_from_html(&html), Some(table));
 
-        assert_eq!(None, Table::find_by_headers("", &headers));
-        assert_eq!(None, Table::find_by_headers("foo", &headers));
-        assert_eq!(None, Table::find_by_headers(HTML_NO_TABLE, &headers));
+        let second = html.select(&css("#second")).next().unwrap();
+        let table = Table::try_from(second).unwrap();
+        assert_eq!(Some("second"), table.id());
 
-        assert_eq!(None, Table::find_by_headers(TABLE_EMPTY, &bad_headers));
-        assert_eq!(None, Table::find_by_headers(TABLE_TH, &bad_headers));
+        let body = html.select(&css("body")).next().unwrap();
+        let table = Table::try_from(body).unwrap();
+        assert_eq!(Some("first"), table.id());
 
-        assert_eq!(None, Table::find_by_headers(TABLE_TD, &headers));
-        assert_eq!(None, Table::find_by_headers(TABLE_TD, &bad_headers));
+        let html = Html::parse_fragment(HTML_NO_TABLE);
+        assert_eq!(Err(TableError::NoTableFound), Table::try_from(&html));
+        let root = html.root_element();
+        assert_eq!(Err(TableError::NoTableFound), Table::try_from(root));
     }
 
     #[test]
-    fn test_find_by_headers_some() {
-        let headers: [&str; 0] = [];
-        assert!(Table::find_by_headers(TABLE_TH, &headers).is_some());
-        assert!(Table::find_by_headers(TABLE_TH_TD, &headers).is_some());
-        assert!(Table::find_by_headers(HTML_TWO_TABLES, &headers).is_some());
+    fn test_select_columns() {
+        let table = Table::find_first(TABLE_COMPLEX).unwrap();
+        let selected = table.select_columns(&[2, 0, 9]);
+        assert_eq!(vec!["Extra", "Name"], selected.header_names());
+        assert_eq!(
+            &[vec!["", "John"], vec!["foo", "May"], vec![], vec!["c", "a"],],
+            selected.data()
+        );
+        let row = selected.row(1).unwrap();
+        assert_eq!(Some("May"), row.get("Name"));
+        assert_eq!(Some("foo"), row.get("Extra"));
+        assert_eq!(
+            table
+                .iter_with_source_row()
+                .map(|(i, _)| i)
+                .collect::<Vec<_>>(),
+            selected
+                .iter_with_source_row()
+                .map(|(i, _)| i)
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(
+            selected,
+            table.select_columns_by_header(&["Extra", "Missing", "Name"])
+        );
+        assert!(table.select_columns(&[]).data().iter().all(Vec::is_empty));
 
-        let headers = ["Name"];
-        assert!(Table::find_by_headers(TABLE_TH, &headers).is_some());
-        assert!(Table::find_by_headers(TABLE_TH_TD, &headers).is_some());
-        assert!(Table::find_by_headers(HTML_TWO_TABLES, &headers).is_some());
+        let html = r#"
+<table>
+    <tr><th>A</th><th colspan="2">B</th><th>A</th></tr>
+    <tr><td>1</td><td>2</td><td>3</td><td>4</td></tr>
+    <tfoot><tr><td>5</td><td>6</td></tr></tfoot>
+</table>
+"#;
+        let table = Table::find_first(html).unwrap();
+        let selected = table.select_columns(&[2, 3, 1]);
+        assert_eq!(vec!["A", "B"], selected.header_names());
+        assert_eq!(&[vec!["3", "4", "2"]], selected.data());
+        assert_eq!(Some("4"), selected.row(0).unwrap().get("A"));
+        assert_eq!(Some("2"), selected.row(0).unwrap().get("B"));
+        let footer: Vec<_> = selected.footer().map(|r| r.as_slice()).collect();
+        assert_eq!(vec![&["", "", "6"]], footer);
+        let header = selected.header_row().unwrap();
+        assert_eq!(&["B", "A", "B"], header.as_slice());
 
-        let headers = ["Age", "Name"];
-        assert!(Table::find_by_headers(TABLE_TH, &headers).is_some());
-        assert!(Table::find_by_headers(TABLE_TH_TD, &headers).is_some());
-        assert!(Table::find_by_headers(HTML_TWO_TABLES, &headers).is_some());
+        let table = TableBuilder::new()
+            .normalize_headers(Some("*"))
+            .find_first("<table><tr><th>X</th><th>Y *</th></tr></table>")
+            .unwrap()
+            .select_columns(&[1]);
+        assert_eq!(Some(&0), table.headers().get("Y"));
+        assert_eq!(None, table.headers().get("Y *"));
+
+        let table = TableBuilder::new()
+            .normalize_headers(Some(":"))
+            .find_first(
+                r#"
+<table>
+    <tr><th>Age</th><th>Name</th><th>Age:</th></tr>
+    <tr><td>1</td><td>John</td><td>2</td></tr>
+</table>
+"#,
+            )
+            .unwrap()
+            .select_columns(&[1, 2]);
+        assert_eq!(Some(&1), table.headers().get("Age"));
+        assert_eq!(Some("2"), table.row(0).unwrap().get("Age"));
     }
 
     #[test]
-    fn test_find_first_incomplete_fragment() {
-        assert!(Table::find_first(HTML_TABLE_FRAGMENT).is_some());
+    fn test_parse_number() {
+        let cases = [
+            ("1234", Some(1234.0)),
+            (" $1,234.50 ", Some(1234.5)),
+            ("€ 1 000", Some(1000.0)),
+            ("£0.99", Some(0.99)),
+            ("¥-5", Some(-5.0)),
+            ("-$3", Some(-3.0)),
+            ("($1,000.25)", Some(-1000.25)),
+            ("( 7 )", Some(-7.0)),
+            ("1.5e3", Some(1500.0)),
+            ("", None),
+            ("$", None),
+            ("()", None),
+            ("12 apples", None),
+            ("(12", None),
+            ("(-5)", None),
+            ("(+5)", None),
+            ("inf", None),
+            ("-infinity", None),
+            ("NaN", None),
+            ("1e400", None),
+        ];
+        for &(cell, expected) in &cases {
+            let html = format!(
+                "<table><tr><th>N</th></tr><tr><td>{}</td></tr></table>",
+                cell
+            );
+            let table = Table::find_first(&html).unwrap();
+            let row = table.iter().next().unwrap();
+            assert_eq!(expected, row.parse_number("N"), "{:?}", cell);
+            assert_eq!(None, row.parse_number("Missing"));
+        }
     }
 
     #[test]
-    fn test_headers_empty() {
-        let empty = HashMap::new();
-        assert_eq!(&empty, Table::find_first(TABLE_TD).unwrap().headers());
-        assert_eq!(&empty, Table::find_first(TABLE_TD_TD).unwrap().headers());
+    fn test_row_id_classes() {
+        let html = r#"
+            <table>
+                <tr id="head"><th>Name</th></tr>
+                <tr id="row-1" class="b a"><td>John</td></tr>
+                <tr><td>May</td></tr>
+                <tfoot><tr class="total"><td>2</td></tr></tfoot>
+            </table>
+        "#;
+        let table = Table::find_first(html).unwrap();
+        let rows: Vec<_> = table.iter().collect();
+        assert_eq!(Some("row-1"), rows[0].id());
+        assert_eq!(vec!["b", "a"], rows[0].classes());
+        assert_eq!(None, rows[1].id());
+        assert!(rows[1].classes().is_empty());
+        assert_eq!(Some("row-1"), table.row(0).unwrap().id());
+        let total = table.footer().next().unwrap();
+        assert_eq!(vec!["total"], total.classes());
+        assert_eq!(None, table.header_row().unwrap().id());
+
+        let owned: Vec<_> = table.clone().into_iter().collect();
+        assert_eq!(Some("row-1"), owned[0].as_row().id());
+
+        let without = table.clone().without_empty_rows();
+        assert_eq!(Some("row-1"), without.iter().next().unwrap().id());
+
+        let mut ids = Vec::new();
+        Table::for_each_row(html, |row| ids.push(row.id().map(String::from)));
+        assert_eq!(vec![Some("row-1".to_string()), None], ids);
+
+        assert_eq!(None, table.transpose().iter().next().unwrap().id());
     }
 
     #[test]
-    fn test_headers_nonempty() {
-        let mut headers = HashMap::new();
-        headers.insert("Name".to_string(), 0);
-        headers.insert("Age".to_string(), 1);
+    fn test_table_in_template() {
+        let table = Table::find_first(HTML_TEMPLATE).unwrap();
+        assert_eq!(Some("20"), table.iter().next().unwrap().get("Age"));
+        let table = Table::find_first_from_document(HTML_TEMPLATE).unwrap();
+        assert_eq!(Some("John"), table.iter().next().unwrap().get("Name"));
+        let table = Table::find_by_headers(HTML_TEMPLATE, &["Age"]).unwrap();
+        assert_eq!(1, table.len());
+    }
 
-        assert_eq!(&headers, Table::find_first(TABLE_TH).unwrap().headers());
-        assert_eq!(&headers, Table::find_first(TABLE_TH_TD).unwrap().headers());
-        assert_eq!(&headers, Table::find_first(TABLE_TH_TH).unwrap().headers());
+    #[test]
+    fn test_combine_headers() {
+        let html = r#"
+            <table>
+                <thead>
+                    <tr>
+                        <th rowspan="2">Name</th>
+                        <th colspan="2">Address</th>
+                        <th colspan="2">Phone</th>
+                    </tr>
+                    <tr>
+                        <th>Street</th><th>City</th><th>Home</th><th></th>
+                    </tr>
+                </thead>
+                <tr><td>John</td><td>Main</td><td>Paris</td><td>1</td><td>2</td></tr>
+            </table>
+        "#;
+        let table = Table::find_first(html).unwrap();
+        assert_eq!(
+            vec!["Name", "Street", "City", "Home", ""],
+            table.header_names()
+        );
 
-        headers.insert("Extra".to_string(), 2);
+        let table = TableBuilder::new()
+            .combine_headers(true)
+            .header_separator(".")
+            .find_first(html)
+            .unwrap();
         assert_eq!(
-            &headers,
-            Table::find_first(TABLE_COMPLEX).unwrap().headers()
+            vec![
+                "Name",
+                "Address.Street",
+                "Address.City",
+                "Phone.Home",
+                "Phone"
+            ],
+            table.header_names()
         );
+        let row = table.iter().next().unwrap();
+        assert_eq!(Some("Paris"), row.get("Address.City"));
+        assert_eq!(Some("2"), row.get("Phone"));
+        assert_eq!(Some("Name"), table.header_row().unwrap().get_index(0));
+
+        // A single header row is unaffected.
+        let table = TableBuilder::new()
+            .combine_headers(true)
+            .find_first(TABLE_TH_TD)
+            .unwrap();
+        assert_eq!(Table::find_first(TABLE_TH_TD), Some(table));
     }
 
     #[test]
-    fn test_iter_empty() {
-        assert_eq!(0, Table::find_first(TABLE_EMPTY).unwrap().iter().count());
-        assert_eq!(0, Table::find_first(TABLE_TH).unwrap().iter().count());
+    fn test_borrowed_table() {
+        let html = Html::parse_fragment(
+            r#"
+            <table>
+                <tr><th>Name</th><th>Notes</th><th>Age</th></tr>
+                <tr><td> John </td><td>a <b>b</b></td><td><b>20</b></td></tr>
+                <tr><td colspan="2">May</td><td></td></tr>
+                <tfoot><tr><td>Total</td></tr></tfoot>
+            </table>
+            "#,
+        );
+        let table = BorrowedTable::find_first_from_html(&html).unwrap();
+        assert_eq!(vec!["Name", "Notes", "Age"], table.header_names());
+        assert_eq!(2, table.len());
+        let row = table.row(0).unwrap();
+        assert!(matches!(row.as_slice()[0], Cow::Borrowed("John")));
+        assert!(matches!(row.as_slice()[1], Cow::Owned(_)));
+        assert!(matches!(row.as_slice()[2], Cow::Borrowed("20")));
+        assert_eq!(Some("a b"), row.get("Notes"));
+        assert_eq!(None, row.get("Weight"));
+
+        let owned = TableBuilder::new()
+            .text_only(true)
+            .expand_spans(false)
+            .build(html.select(&css("table")).next().unwrap());
+        assert_eq!(owned.clone().into_data(), table.to_table().into_data());
+        assert_eq!(owned.headers(), table.headers());
+        let rows: Vec<Vec<&str>> = table
+            .iter()
+            .map(|row| {
+                (0..row.len()).filter_map(|i| row.get_index(i)).collect()
+            })
+            .collect();
+        assert_eq!(vec![vec!["John", "a b", "20"], vec!["May", ""]], rows);
+
+        let empty = Html::parse_fragment(HTML_NO_TABLE);
+        assert_eq!(None, BorrowedTable::find_first_from_html(&empty));
     }
 
     #[test]
-    fn test_iter_nonempty() {
-        assert_eq!(1, Table::find_first(TABLE_TD).unwrap().iter().count());
-        assert_eq!(1, Table::find_first(TABLE_TH_TD).unwrap().iter().count());
-        assert_eq!(2, Table::find_first(TABLE_TD_TD).unwrap().iter().count());
-        assert_eq!(1, Table::find_first(TABLE_TH_TH).unwrap().iter().count());
-        assert_eq!(4, Table::find_first(TABLE_COMPLEX).unwrap().iter().count());
+    fn test_display() {
+        let table = Table::find_first(TABLE_TH_TD).unwrap();
+        assert_eq!(table.to_ascii(), format!("{}", table));
+        assert!(format!("{:?}", table).starts_with("Table {"));
+        let empty = Table::find_first(TABLE_EMPTY).unwrap();
+        assert_eq!("", empty.to_string());
     }
 
     #[test]
-    fn test_row_is_empty() {
+    fn test_num_header_columns() {
+        let table = Table::find_first(TABLE_COMPLEX).unwrap();
+        assert_eq!(3, table.num_header_columns());
+        assert_eq!(4, table.num_columns());
+
         let table = Table::find_first(TABLE_TD).unwrap();
-        assert_eq!(
-            vec![false],
-            table.iter().map(|r| r.is_empty()).collect::<Vec<_>>()
-        );
+        assert_eq!(0, table.num_header_columns());
 
+        let html = "<table><tr><th>A</th><th>A</th><th>B</th></tr></table>";
+        let table = Table::find_first(html).unwrap();
+        assert_eq!(2, table.num_header_columns());
+        assert_eq!(3, table.num_columns());
+    }
+
+    #[test]
+    fn test_iter_named() {
         let table = Table::find_first(TABLE_COMPLEX).unwrap();
+        let named: Vec<Vec<_>> =
+            table.iter().map(|row| row.iter_named().collect()).collect();
         assert_eq!(
-            vec![false, false, true, false],
-            table.iter().map(|r| r.is_empty()).collect::<Vec<_>>()
+            vec![
+                vec![("Name", "John"), ("Age", "20")],
+                vec![("Name", "May"), ("Age", "30"), ("Extra", "foo")],
+                vec![],
+                vec![("Name", "a"), ("Age", "b"), ("Extra", "c")],
+            ],
+            named
         );
+
+        let html = r#"
+            <table>
+                <tr><th colspan="2">Phone</th><th></th><th>Phone</th></tr>
+                <tr><td>1</td><td>2</td><td>3</td><td>4</td></tr>
+            </table>
+        "#;
+        let table = Table::find_first(html).unwrap();
+        let row = table.iter().next().unwrap();
+        let named: Vec<_> = row.iter_named().collect();
+        assert_eq!(vec![("Phone", "1"), ("Phone", "2"), ("Phone", "4")], named);
+        assert!(Table::find_first(TABLE_TD)
+            .unwrap()
+            .iter()
+            .all(|row| { row.iter_named().next().is_none() }));
     }
 
     #[test]
-    fn test_row_len() {
-        let table = Table::find_first(TABLE_TD).unwrap();
-        assert_eq!(vec![2], table.iter().map(|r| r.len()).collect::<Vec<_>>());
+    fn test_find_first_data_table() {
+        let first = Table::find_first(HTML_LAYOUT).unwrap();
+        assert_eq!(Some("Home"), first.cell(0, 0));
+
+        let table = Table::find_first_data_table(HTML_LAYOUT).unwrap();
+        assert_eq!(Some("John"), table.cell(0, 0));
+
+        let filter = DataTableFilter::new().min_columns(1);
+        assert_eq!(
+            Some("Menu"),
+            filter.find_first(HTML_LAYOUT).unwrap().cell(0, 0)
+        );
+
+        let filter = DataTableFilter::new().min_rows(3);
+        assert_eq!(None, filter.find_first(HTML_LAYOUT));
+
+        // A header row is enough to make a single column table hold data.
+        let html =
+            "<table><tr><th>Name</th></tr><tr><td>John</td></tr></table>";
+        let table = Table::find_first_data_table(html).unwrap();
+        assert_eq!(Table::find_first(html), Some(table));
+        assert_eq!(None, Table::find_first_data_table(TABLE_EMPTY));
+    }
 
+    #[test]
+    fn test_to_columns() {
         let table = Table::find_first(TABLE_COMPLEX).unwrap();
+        let columns = table.to_columns();
+        assert_eq!(4, columns.len());
         assert_eq!(
-            vec![2, 3, 0, 4],
-            table.iter().map(|r| r.len()).collect::<Vec<_>>()
+            ("Name", vec![Some("John"), Some("May"), None, Some("a")]),
+            columns[0]
         );
+        assert_eq!(
+            ("Extra", vec![None, Some("foo"), None, Some("c")]),
+            columns[2]
+        );
+        assert_eq!(("", vec![None, None, None, Some("d")]), columns[3]);
+
+        let table = Table::find_first(TABLE_EMPTY).unwrap();
+        assert!(table.to_columns().is_empty());
     }
 
+    #[cfg(feature = "polars")]
     #[test]
-    fn test_row_get_without_headers() {
-        let table = Table::find_first(TABLE_TD).unwrap();
-        let mut iter = table.iter();
-        let row = iter.next().unwrap();
+    fn test_into_dataframe() {
+        use polars::prelude::*;
 
-        assert_eq!(None, row.get(""));
-        assert_eq!(None, row.get("foo"));
-        assert_eq!(None, row.get("Name"));
-        assert_eq!(None, row.get("Age"));
+        let table = Table::find_first(TABLE_COMPLEX).unwrap();
+        let df = table.into_dataframe().unwrap();
+        assert_eq!((4, 4), df.shape());
+        assert_eq!(table.len(), df.height());
+        let names: Vec<&str> =
+            df.get_column_names().iter().map(|n| n.as_str()).collect();
+        assert_eq!(vec!["Name", "Age", "Extra", ""], names);
+        let extra = df.column("Extra").unwrap();
+        assert_eq!(&DataType::String, extra.dtype());
+        let extra = extra.str().unwrap();
+        let extra: Vec<Option<&str>> = (0..4).map(|i| extra.get(i)).collect();
+        assert_eq!(vec![None, Some("foo"), None, Some("c")], extra);
 
-        assert_eq!(None, iter.next());
+        let table = Table::find_first(TABLE_EMPTY).unwrap();
+        assert_eq!((0, 0), table.into_dataframe().unwrap().shape());
+
+        let table = Table::find_first(TABLE_TD_TD).unwrap();
+        assert!(table.into_dataframe().is_err());
     }
 
     #[test]
-    fn test_row_get_with_headers() {
+    fn test_to_padded_data() {
+        let table = Table::find_first(TABLE_COMPLEX).unwrap();
+        let data = table.to_padded_data();
+        assert!(data.iter().all(|row| row.len() == 4));
+        assert_eq!(vec!["John", "20", "", ""], data[0]);
+        assert_eq!(vec!["", "", "", ""], data[2]);
+        assert_eq!(vec!["a", "b", "c", "d"], data[3]);
+
         let table = Table::find_first(TABLE_TH_TD).unwrap();
-        let mut iter = table.iter();
-        let row = iter.next().unwrap();
+        assert_eq!(table.data(), &table.to_padded_data()[..]);
+    }
 
-        assert_eq!(None, row.get(""));
-        assert_eq!(None, row.get("foo"));
-        assert_eq!(Some("John"), row.get("Name"));
-        assert_eq!(Some("20"), row.get("Age"));
+    #[cfg(feature = "ndarray")]
+    #[test]
+    fn test_to_array2() {
+        let table = Table::find_first(TABLE_COMPLEX).unwrap();
+        let array = table.to_array2();
+        assert_eq!(&[4, 4], array.shape());
+        for (i, row) in table.to_padded_data().iter().enumerate() {
+            assert_eq!(row.as_slice(), array.row(i).as_slice().unwrap());
+        }
 
-        assert_eq!(None, iter.next());
+        let table = Table::find_first(TABLE_EMPTY).unwrap();
+        assert!(table.to_array2().is_empty());
     }
 
+    #[cfg(feature = "ndarray")]
     #[test]
-    fn test_row_get_complex() {
-        let table = Table::find_first(TABLE_COMPLEX).unwrap();
-        let mut iter = table.iter();
+    fn test_to_array2_f64() {
+        let html = r#"
+            <table>
+                <tr><th>X</th><th>Y</th></tr>
+                <tr><td>1</td><td>-2.5</td></tr>
+                <tr><td>3</td></tr>
+            </table>
+        "#;
+        let table = Table::find_first(html).unwrap();
+        assert_eq!(
+            Err(RowError::MissingColumn("Y".to_string())),
+            table.to_array2_f64()
+        );
 
-        let row = iter.next().unwrap();
-        assert_eq!(Some("John"), row.get("Name"));
-        assert_eq!(Some("20"), row.get("Age"));
-        assert_eq!(None, row.get("Extra"));
+        let table = Table::find_first(TABLE_TH_TD).unwrap();
+        assert_eq!(
+            Err(RowError::InvalidValue {
+                header: "Name".to_string(),
+                value: "John".to_string(),
+                message: "invalid float literal".to_string(),
+            }),
+            table.to_array2_f64()
+        );
 
-        let row = iter.next().unwrap();
-        assert_eq!(Some("May"), row.get("Name"));
-        assert_eq!(Some("30"), row.get("Age"));
-        assert_eq!(Some("foo"), row.get("Extra"));
+        let html = html.replace("<td>3</td>", "<td>3</td><td>4</td>");
+        let table = Table::find_first(&html).unwrap();
+        let array = table.to_array2_f64().unwrap();
+        assert_eq!(ndarray::arr2(&[[1.0, -2.5], [3.0, 4.0]]), array);
+    }
 
-        let row = iter.next().unwrap();
-        assert_eq!(None, row.get("Name"));
-        assert_eq!(None, row.get("Age"));
-        assert_eq!(None, row.get("Extra"));
+    #[test]
+    fn test_header_search_rows() {
+        assert_eq!(None, Table::find_by_headers(TABLE_TITLE_ROW, &["Age"]));
+        let options = TableBuilder::new().header_search_rows(2);
+        assert_eq!(None, options.find_by_headers(TABLE_TITLE_ROW, &["Age"]));
 
-        let row = iter.next().unwrap();
-        assert_eq!(Some("a"), row.get("Name"));
-        assert_eq!(Some("b"), row.get("Age"));
-        assert_eq!(Some("c"), row.get("Extra"));
+        let options = TableBuilder::new().header_search_rows(3);
+        let table = options.find_by_headers(TABLE_TITLE_ROW, &["Age"]).unwrap();
+        assert_eq!(vec!["Name", "Age"], table.header_names());
+        assert_eq!(1, table.len());
+        assert_eq!(Some("John"), table.iter().next().unwrap().get("Name"));
 
-        assert_eq!(None, iter.next());
+        // The first row is still found without a header row option.
+        let options = TableBuilder::new().header_search_rows(3);
+        let table = options.find_by_headers(TABLE_TITLE_ROW, &["People"]);
+        assert_eq!(Table::find_first(TABLE_TITLE_ROW), table);
+
+        let html = format!("{}{}", TABLE_TH, TABLE_TITLE_ROW);
+        let table = options.find_by_headers(&html, &["Age"]).unwrap();
+        assert!(table.is_empty());
     }
 
     #[test]
-    fn test_row_as_slice_without_headers() {
+    fn test_header_slice() {
+        let table = Table::find_first(TABLE_COMPLEX).unwrap();
+        assert_eq!(["Name", "Age", "Extra"], table.header_slice());
+        assert_eq!(table.header_names(), table.header_slice());
+
         let table = Table::find_first(TABLE_TD).unwrap();
-        let mut iter = table.iter();
+        assert!(table.header_slice().is_empty());
 
-        assert_eq!(&["Name", "Age"], iter.next().unwrap().as_slice());
-        assert_eq!(None, iter.next());
+        let table = Table::find_first(TABLE_COMPLEX)
+            .unwrap()
+            .select_columns(&[2, 0]);
+        assert_eq!(["Extra", "Name"], table.header_slice());
     }
 
     #[test]
-    fn test_row_as_slice_with_headers() {
-        let table = Table::find_first(TABLE_TH_TD).unwrap();
-        let mut iter = table.iter();
+    fn test_ascii_fold() {
+        let html = r#"
+            <table>
+                <tr><th>Año</th><th>Straße</th><th>Año</th><th>Œuvre</th></tr>
+                <tr><td>2020</td><td>Hauptstraße</td><td>2021</td><td>x</td></tr>
+            </table>
+        "#;
+        let table = Table::find_first(html).unwrap();
+        assert_eq!(None, table.iter().next().unwrap().get("Ano"));
 
-        assert_eq!(&["John", "20"], iter.next().unwrap().as_slice());
-        assert_eq!(None, iter.next());
+        let table = TableBuilder::new()
+            .ascii_fold(true)
+            .find_first(html)
+            .unwrap();
+        let row = table.iter().next().unwrap();
+        assert_eq!(Some("2020"), row.get("Ano"));
+        assert_eq!(Some("2020"), row.get("AÑO"));
+        assert_eq!(Some("Hauptstraße"), row.get("strasse"));
+        assert_eq!(Some("x"), row.get("oeuvre"));
+        assert_eq!(Some("Hauptstraße"), row.cell("STRASSE").map(Cell::text));
+        assert_eq!(vec!["Año", "Straße", "Año", "Œuvre"], table.header_names());
+        assert_eq!(Some(vec!["2020"]), table.column("ano"));
+        assert!(table.headers().contains_key("ano"));
+        assert_eq!(vec!["2020", "2021"], row.get_all("ano"));
+        assert_eq!(vec!["2020", "2021"], row.get_all("Año"));
+        assert_eq!(Some("2020"), row.get_ci("ANO"));
+        assert_eq!(Some("Hauptstraße"), row.get_normalized(" Strasse "));
+        let named: Vec<_> = row.iter_named().map(|(name, _)| name).collect();
+        assert_eq!(vec!["ano", "strasse", "ano", "oeuvre"], named);
+
+        let selected = table.select_columns_by_header(&["oeuvre", "ANO"]);
+        assert_eq!(vec!["Œuvre", "Año"], selected.header_names());
+        let row = selected.iter().next().unwrap();
+        assert_eq!(Some("2020"), row.get("Año"));
+
+        // Combining marks are removed as well as precomposed letters.
+        assert_eq!("ano", fold_header("An\u{303}o"));
+        assert_eq!("ano", fold_header("Año"));
+        assert_eq!("日本", fold_header("日本"));
     }
 
     #[test]
-    fn test_row_as_slice_complex() {
+    fn test_get_owned() {
+        let names: Vec<String> = {
+            let table = Table::find_first(TABLE_COMPLEX).unwrap();
+            table
+                .iter()
+                .filter_map(|row| row.get_owned("Name"))
+                .collect()
+        };
+        assert_eq!(vec!["John", "May", "a"], names);
         let table = Table::find_first(TABLE_COMPLEX).unwrap();
-        let mut iter = table.iter();
-        let empty: [&str; 0] = [];
+        let row = table.iter().next().unwrap();
+        assert_eq!(None, row.get_owned("Extra"));
+        assert_eq!(None, row.get_owned("Weight"));
+    }
 
-        assert_eq!(&["John", "20"], iter.next().unwrap().as_slice());
-        assert_eq!(&["May", "30", "foo"], iter.next().unwrap().as_slice());
-        assert_eq!(&empty, iter.next().unwrap().as_slice());
-        assert_eq!(&["a", "b", "c", "d"], iter.next().unwrap().as_slice());
-        assert_eq!(None, iter.next());
+    #[test]
+    fn test_diagnostics() {
+        let diagnostics = Table::diagnostics(HTML_NO_TABLE);
+        assert_eq!(0, diagnostics.num_tables());
+
+        let diagnostics = Table::diagnostics(HTML_TWO_TABLES);
+        assert_eq!(2, diagnostics.num_tables());
+        let tables = diagnostics.tables();
+        assert_eq!(Some("first"), tables[0].id());
+        assert_eq!(&["Name", "Weight"], tables[1].header_cells());
+        assert_eq!(2, tables[1].num_rows());
+
+        let diagnostics = Table::diagnostics(TABLE_TITLE_ROW);
+        let summary = &diagnostics.tables()[0];
+        assert_eq!(&["People"], summary.header_cells());
+        assert_eq!(5, summary.num_rows());
+        assert_eq!(1, summary.num_footer_rows());
+
+        let html = "<table><tr><td><table><tr><th>A</th></tr></table></td></tr></table>";
+        let diagnostics = Table::diagnostics(html);
+        let tables = diagnostics.tables();
+        assert_eq!(2, tables.len());
+        assert!(tables[0].header_cells().is_empty());
+        assert_eq!((1, 1), (tables[0].num_rows(), tables[1].num_rows()));
     }
 
     #[test]
-    fn test_row_iter_simple() {
-        let table = Table::find_first(TABLE_TD).unwrap();
-        let row = table.iter().next().unwrap();
-        let mut iter = row.iter();
+    fn test_find_first_under() {
+        let html = HTML_COMPLEX_JUNK_WITH_TABLES;
+        let table = Table::find_first_under(html, "div#main_page", "table");
+        let row = table.unwrap().iter().next().unwrap().get_owned("Name");
+        assert_eq!(Some("John".to_string()), row);
 
-        assert_eq!(Some("Name"), iter.next().map(String::as_str));
-        assert_eq!(Some("Age"), iter.next().map(String::as_str));
-        assert_eq!(None, iter.next());
+        // The first section has no table, so the second one is used.
+        let table = Table::find_first_under(html, "div.section", "table");
+        assert_eq!(
+            Some("Name"),
+            table.unwrap().header_names().first().copied()
+        );
+
+        let table = Table::find_first_under(
+            html,
+            "#main_page",
+            "table.data:nth-of-type(2)",
+        );
+        assert_eq!(Some("May"), table.unwrap().cell(0, 0));
+
+        assert_eq!(None, Table::find_first_under(html, "div#missing", "table"));
+        assert_eq!(
+            None,
+            Table::find_first_under(html, "div.header", "table.data")
+        );
+        assert_eq!(None, Table::find_first_under(html, "div[", "table"));
+        assert_eq!(None, Table::find_first_under(html, "div", "table["));
     }
 
     #[test]
-    fn test_row_iter_complex() {
+    fn test_chunks() {
         let table = Table::find_first(TABLE_COMPLEX).unwrap();
-        let mut table_iter = table.iter();
+        let sizes: Vec<_> = table.chunks(3).map(|chunk| chunk.len()).collect();
+        assert_eq!(vec![3, 1], sizes);
+        let chunks: Vec<_> = table.chunks(2).collect();
+        assert_eq!(2, chunks.len());
+        assert_eq!(Some("May"), chunks[0][1].get("Name"));
+        assert_eq!(table.iter().collect::<Vec<_>>(), chunks.concat());
+        assert_eq!(1, table.chunks(10).count());
 
-        let row = table_iter.next().unwrap();
-        let mut iter = row.iter();
-        assert_eq!(Some("John"), iter.next().map(String::as_str));
-        assert_eq!(Some("20"), iter.next().map(String::as_str));
-        assert_eq!(None, iter.next());
+        let empty = Table::find_first(TABLE_EMPTY).unwrap();
+        assert_eq!(0, empty.chunks(2).count());
+    }
 
-        let row = table_iter.next().unwrap();
-        let mut iter = row.iter();
-        assert_eq!(Some("May"), iter.next().map(String::as_str));
-        assert_eq!(Some("30"), iter.next().map(String::as_str));
-        assert_eq!(Some("foo"), iter.next().map(String::as_str));
-        assert_eq!(None, iter.next());
+    #[test]
+    #[should_panic(expected = "chunk size must be non-zero")]
+    fn test_chunks_zero() {
+        let table = Table::find_first(TABLE_COMPLEX).unwrap();
+        let _ = table.chunks(0);
+    }
 
-        let row = table_iter.next().unwrap();
-        let mut iter = row.iter();
-        assert_eq!(None, iter.next());
+    #[test]
+    fn test_row_data_attrs() {
+        let html = r#"
+            <table>
+                <tr data-header="yes"><th>Name</th></tr>
+                <tr data-id="1" data-status="active" title="x"><td>John</td></tr>
+                <tr><td>May</td></tr>
+                <tfoot><tr data-total=""><td>2</td></tr></tfoot>
+            </table>
+        "#;
+        let table = Table::find_first(html).unwrap();
+        assert!(table.iter().all(|row| row.data_attrs().is_empty()));
 
-        let row = table_iter.next().unwrap();
-        let mut iter = row.iter();
-        assert_eq!(Some("a"), iter.next().map(String::as_str));
-        assert_eq!(Some("b"), iter.next().map(String::as_str));
-        assert_eq!(Some("c"), iter.next().map(String::as_str));
-        assert_eq!(Some("d"), iter.next().map(String::as_str));
-        assert_eq!(None, iter.next());
+        let options = TableBuilder::new().row_data_attrs(true);
+        let table = options.find_first(html).unwrap();
+        let rows: Vec<_> = table.iter().collect();
+        let mut expected = HashMap::new();
+        expected.insert("data-id", "1");
+        expected.insert("data-status", "active");
+        assert_eq!(expected, rows[0].data_attrs());
+        assert!(rows[1].data_attrs().is_empty());
+        let total = table.footer().next().unwrap().data_attrs();
+        assert_eq!(Some(&""), total.get("data-total"));
+        assert!(table.header_row().unwrap().data_attrs().is_empty());
+
+        let mut ids = Vec::new();
+        options.for_each_row(html, |row| {
+            ids.push(row.data_attrs().get("data-id").map(|id| id.to_string()))
+        });
+        assert_eq!(vec![Some("1".to_string()), None], ids);
     }
 }